@@ -4,6 +4,10 @@ use bevy_inspector_egui::InspectorOptions;
 use bevy_prototype_debug_lines::DebugLinesPlugin;
 use bevy_rapier2d::prelude::*;
 use bevy_rapier2d::prelude::{ExternalImpulse, KinematicCharacterController};
+use input_map::{
+    actions::{Action, ActionState},
+    InputMapPlugin,
+};
 use pancam::*;
 use physics_2d_plugin::*;
 
@@ -16,8 +20,10 @@ fn main() {
         .add_plugin(WorldInspectorPlugin::default())
         .add_plugin(DebugLinesPlugin::default())
         .insert_resource(Msaa::Sample4)
+        .add_plugin(InputMapPlugin)
         // OnEnter State Systems
         .add_startup_system(setup)
+        .add_startup_system(setup_physics)
         .add_system(player_pull_movement)
         .add_system(bevy::window::close_on_esc)
         .run();
@@ -89,37 +95,20 @@ pub struct Player {
 }
 
 pub fn player_pull_movement(
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<ActionState>,
     mut player_query: Query<(&mut Player, &mut Velocity)>,
     time: Res<Time>,
 ) {
     for (player, mut velocity) in player_query.iter_mut() {
-        let mut direction = Vec3::ZERO;
-
-        let key_directions = [
-            (KeyCode::Left, Vec3::new(-1.0, 0.0, 0.0)),
-            (KeyCode::A, Vec3::new(-1.0, 0.0, 0.0)),
-            (KeyCode::Right, Vec3::new(1.0, 0.0, 0.0)),
-            (KeyCode::D, Vec3::new(1.0, 0.0, 0.0)),
-            (KeyCode::Up, Vec3::new(0.0, 1.0, 0.0)),
-            (KeyCode::W, Vec3::new(0.0, 1.0, 0.0)),
-            (KeyCode::Down, Vec3::new(0.0, -1.0, 0.0)),
-            (KeyCode::S, Vec3::new(0.0, -1.0, 0.0)),
-        ];
-
-        for (key_code, dir) in key_directions.iter() {
-            if keyboard_input.pressed(*key_code) {
-                direction += *dir;
-            }
-        }
-
-        if direction.length() > 0.0 {
-            direction = direction.normalize()
-        }
+        let direction = Vec2::new(
+            actions.axis(Action::RotateHullLeft, Action::RotateHullRight),
+            actions.axis(Action::MoveBackward, Action::MoveForward),
+        )
+        .clamp_length_max(1.0);
 
         let speed = player.speed;
         let delta_time = time.delta_seconds();
 
-        velocity.linvel = direction.truncate() * speed * delta_time;
+        velocity.linvel = direction * speed * delta_time;
     }
 }
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+/// How strongly an action has to be held to count as pressed.
+pub const PRESS_THRESHOLD: f32 = 0.5;
+
+/// Something the player can do, independent of the keys, buttons or sticks bound to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Action {
+    MoveForward,
+    MoveBackward,
+    RotateHullLeft,
+    RotateHullRight,
+    AimUp,
+    AimDown,
+    AimLeft,
+    AimRight,
+    Fire,
+    Pause,
+    StartGame,
+    MainMenu,
+    ToggleFlowField,
+}
+
+impl Action {
+    /// Every action, in the order the controls screen lists them.
+    pub const ALL: [Action; 13] = [
+        Action::MoveForward,
+        Action::MoveBackward,
+        Action::RotateHullLeft,
+        Action::RotateHullRight,
+        Action::AimUp,
+        Action::AimDown,
+        Action::AimLeft,
+        Action::AimRight,
+        Action::Fire,
+        Action::Pause,
+        Action::StartGame,
+        Action::MainMenu,
+        Action::ToggleFlowField,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MoveForward => "Move forward",
+            Action::MoveBackward => "Move backward",
+            Action::RotateHullLeft => "Rotate hull left",
+            Action::RotateHullRight => "Rotate hull right",
+            Action::AimUp => "Aim up",
+            Action::AimDown => "Aim down",
+            Action::AimLeft => "Aim left",
+            Action::AimRight => "Aim right",
+            Action::Fire => "Fire",
+            Action::Pause => "Pause",
+            Action::StartGame => "Start game",
+            Action::MainMenu => "Main menu",
+            Action::ToggleFlowField => "Flow field arrows",
+        }
+    }
+}
+
+/// How strongly every action is held this frame, from 0 to 1.
+///
+/// Updated before `Update` from `InputBindings`, so all systems of a frame see the same
+/// state.
+#[derive(Resource, Default, Debug)]
+pub struct ActionState {
+    current: BTreeMap<Action, f32>,
+    previous: BTreeMap<Action, f32>,
+}
+
+impl ActionState {
+    /// Starts a new frame. An action bound to several inputs takes the strongest one.
+    pub fn update(&mut self, values: impl IntoIterator<Item = (Action, f32)>) {
+        self.previous = std::mem::take(&mut self.current);
+
+        for (action, value) in values {
+            let current = self.current.entry(action).or_default();
+            *current = current.max(value.clamp(0.0, 1.0));
+        }
+    }
+
+    pub fn value(&self, action: Action) -> f32 {
+        self.current.get(&action).copied().unwrap_or_default()
+    }
+
+    /// The difference between two opposing actions, from -1 to 1.
+    pub fn axis(&self, negative: Action, positive: Action) -> f32 {
+        self.value(positive) - self.value(negative)
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.value(action) >= PRESS_THRESHOLD
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        let previous = self.previous.get(&action).copied().unwrap_or_default();
+        self.pressed(action) && previous < PRESS_THRESHOLD
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn just_pressed_lasts_one_frame() {
+        let mut state = ActionState::default();
+
+        state.update([(Action::Fire, 1.0)]);
+        assert!(state.pressed(Action::Fire));
+        assert!(state.just_pressed(Action::Fire));
+
+        state.update([(Action::Fire, 1.0)]);
+        assert!(state.pressed(Action::Fire));
+        assert!(!state.just_pressed(Action::Fire));
+
+        state.update([(Action::Fire, 0.0)]);
+        assert!(!state.pressed(Action::Fire));
+    }
+
+    #[test]
+    fn strongest_binding_wins() {
+        let mut state = ActionState::default();
+
+        state.update([(Action::MoveForward, 0.3), (Action::MoveForward, 1.0)]);
+        assert_eq!(state.value(Action::MoveForward), 1.0);
+
+        state.update([(Action::MoveForward, 0.3), (Action::MoveForward, 0.0)]);
+        assert_eq!(state.value(Action::MoveForward), 0.3);
+        assert!(!state.pressed(Action::MoveForward));
+    }
+
+    #[test]
+    fn axis_combines_opposing_actions() {
+        let mut state = ActionState::default();
+
+        state.update([
+            (Action::RotateHullLeft, 1.0),
+            (Action::RotateHullRight, 0.25),
+        ]);
+
+        assert_eq!(
+            state.axis(Action::RotateHullLeft, Action::RotateHullRight),
+            -0.75
+        );
+        assert_eq!(state.axis(Action::MoveBackward, Action::MoveForward), 0.0);
+    }
+}
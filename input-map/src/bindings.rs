@@ -0,0 +1,337 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::actions::Action;
+
+/// The bindings the game ships with, relative to the working directory.
+pub const BINDINGS_PATH: &str = "assets/config/bindings.ron";
+
+/// How many bindings per kind of device each action can have on the controls screen,
+/// like W and Up for moving forward.
+pub const BINDING_SLOTS: usize = 2;
+
+/// Where the player's own bindings are kept, in their config directory.
+pub fn user_bindings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("tanki").join("bindings.ron"))
+}
+
+/// A key, button or stick direction that triggers an action.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    /// On the player's gamepad.
+    GamepadButton(GamepadButtonType),
+    /// Pushing a stick the given way, on the player's gamepad.
+    GamepadAxis(GamepadAxisType, AxisDirection),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AxisDirection {
+    Positive,
+    Negative,
+}
+
+impl Binding {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Binding::GamepadButton(_) | Binding::GamepadAxis(_, _))
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Binding::Key(key_code) => write!(f, "{key_code:?}"),
+            Binding::Mouse(button) => write!(f, "Mouse {button:?}"),
+            Binding::GamepadButton(button_type) => write!(f, "Pad {button_type:?}"),
+            Binding::GamepadAxis(axis_type, AxisDirection::Positive) => {
+                write!(f, "Pad {axis_type:?}+")
+            }
+            Binding::GamepadAxis(axis_type, AxisDirection::Negative) => {
+                write!(f, "Pad {axis_type:?}-")
+            }
+        }
+    }
+}
+
+impl AxisDirection {
+    /// How strongly an axis at `value` is pushed this way, from 0 to 1.
+    pub fn strength(&self, value: f32) -> f32 {
+        match self {
+            AxisDirection::Positive => value.max(0.0),
+            AxisDirection::Negative => (-value).max(0.0),
+        }
+    }
+}
+
+/// The gamepad assigned to the player, if one is connected.
+#[derive(Resource, Default)]
+pub struct ActiveGamepad(pub Option<Gamepad>);
+
+/// Which inputs trigger each action.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InputBindings(pub BTreeMap<Action, Vec<Binding>>);
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use AxisDirection::*;
+
+        Self(BTreeMap::from([
+            (
+                Action::MoveForward,
+                vec![
+                    Binding::Key(KeyCode::W),
+                    Binding::Key(KeyCode::Up),
+                    Binding::GamepadButton(GamepadButtonType::DPadUp),
+                    Binding::GamepadAxis(GamepadAxisType::LeftStickY, Positive),
+                ],
+            ),
+            (
+                Action::MoveBackward,
+                vec![
+                    Binding::Key(KeyCode::S),
+                    Binding::Key(KeyCode::Down),
+                    Binding::GamepadButton(GamepadButtonType::DPadDown),
+                    Binding::GamepadAxis(GamepadAxisType::LeftStickY, Negative),
+                ],
+            ),
+            (
+                Action::RotateHullLeft,
+                vec![
+                    Binding::Key(KeyCode::A),
+                    Binding::Key(KeyCode::Left),
+                    Binding::GamepadButton(GamepadButtonType::DPadLeft),
+                    Binding::GamepadAxis(GamepadAxisType::LeftStickX, Negative),
+                ],
+            ),
+            (
+                Action::RotateHullRight,
+                vec![
+                    Binding::Key(KeyCode::D),
+                    Binding::Key(KeyCode::Right),
+                    Binding::GamepadButton(GamepadButtonType::DPadRight),
+                    Binding::GamepadAxis(GamepadAxisType::LeftStickX, Positive),
+                ],
+            ),
+            (
+                Action::AimUp,
+                vec![Binding::GamepadAxis(GamepadAxisType::RightStickY, Positive)],
+            ),
+            (
+                Action::AimDown,
+                vec![Binding::GamepadAxis(GamepadAxisType::RightStickY, Negative)],
+            ),
+            (
+                Action::AimLeft,
+                vec![Binding::GamepadAxis(GamepadAxisType::RightStickX, Negative)],
+            ),
+            (
+                Action::AimRight,
+                vec![Binding::GamepadAxis(GamepadAxisType::RightStickX, Positive)],
+            ),
+            (
+                Action::Fire,
+                vec![
+                    Binding::Mouse(MouseButton::Left),
+                    Binding::GamepadButton(GamepadButtonType::RightTrigger2),
+                ],
+            ),
+            (
+                Action::Pause,
+                vec![
+                    Binding::Key(KeyCode::Escape),
+                    Binding::GamepadButton(GamepadButtonType::Start),
+                ],
+            ),
+            (Action::StartGame, vec![Binding::Key(KeyCode::G)]),
+            (
+                Action::MainMenu,
+                vec![
+                    Binding::Key(KeyCode::M),
+                    Binding::GamepadButton(GamepadButtonType::Select),
+                ],
+            ),
+            (Action::ToggleFlowField, vec![Binding::Key(KeyCode::F)]),
+        ]))
+    }
+}
+
+impl InputBindings {
+    /// Actions missing from the file keep their default bindings.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+        let mut bindings: Self = ron::from_str(&text).map_err(|error| error.to_string())?;
+
+        for (action, defaults) in Self::default().0 {
+            bindings.0.entry(action).or_insert(defaults);
+        }
+
+        Ok(bindings)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let text = ron::ser::to_string_pretty(self, Default::default())
+            .map_err(|error| error.to_string())?;
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+        }
+        std::fs::write(path, text).map_err(|error| error.to_string())
+    }
+
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.0.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// The bindings of `action` in `slot`, at most one per kind of device.
+    pub fn slot(&self, action: Action, slot: usize) -> Vec<Binding> {
+        [false, true]
+            .into_iter()
+            .filter_map(|gamepad| {
+                self.get(action)
+                    .iter()
+                    .filter(|binding| binding.is_gamepad() == gamepad)
+                    .nth(slot)
+                    .copied()
+            })
+            .collect()
+    }
+
+    /// Replaces the binding of `action` in `slot` on the same kind of device as
+    /// `binding`, or adds it when that slot is empty. The other slots keep theirs, so
+    /// rebinding W keeps Up and the gamepad bindings.
+    pub fn rebind(&mut self, action: Action, slot: usize, binding: Binding) {
+        let bindings = self.0.entry(action).or_default();
+        let existing = bindings
+            .iter()
+            .enumerate()
+            .filter(|(_, existing)| existing.is_gamepad() == binding.is_gamepad())
+            .nth(slot)
+            .map(|(index, _)| index);
+
+        match existing {
+            Some(index) => bindings[index] = binding,
+            None => bindings.push(binding),
+        }
+    }
+
+    /// Bindings that trigger more than one action, with the actions they trigger.
+    pub fn conflicts(&self) -> Vec<(Binding, Vec<Action>)> {
+        let mut conflicts: Vec<(Binding, Vec<Action>)> = Vec::new();
+
+        for (action, binding) in self.iter() {
+            match conflicts
+                .iter_mut()
+                .find(|(existing, _)| existing == binding)
+            {
+                Some((_, actions)) if !actions.contains(&action) => actions.push(action),
+                Some(_) => {}
+                None => conflicts.push((*binding, vec![action])),
+            }
+        }
+
+        conflicts.retain(|(_, actions)| actions.len() > 1);
+        conflicts
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Action, &Binding)> {
+        self.0
+            .iter()
+            .flat_map(|(action, bindings)| bindings.iter().map(|binding| (*action, binding)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings_file_matches_defaults() {
+        let bindings: InputBindings =
+            ron::from_str(include_str!("../../assets/config/bindings.ron")).unwrap();
+
+        assert_eq!(bindings, InputBindings::default());
+    }
+
+    #[test]
+    fn defaults_have_no_conflicts() {
+        assert!(InputBindings::default().conflicts().is_empty());
+    }
+
+    #[test]
+    fn rebinding_a_key_keeps_gamepad_bindings() {
+        let mut bindings = InputBindings::default();
+
+        bindings.rebind(Action::Fire, 0, Binding::Key(KeyCode::Space));
+
+        assert_eq!(
+            bindings.get(Action::Fire),
+            [
+                Binding::Key(KeyCode::Space),
+                Binding::GamepadButton(GamepadButtonType::RightTrigger2),
+            ]
+        );
+    }
+
+    #[test]
+    fn rebinding_one_key_keeps_the_other() {
+        let mut bindings = InputBindings::default();
+
+        bindings.rebind(Action::MoveForward, 0, Binding::Key(KeyCode::I));
+
+        assert_eq!(
+            bindings.slot(Action::MoveForward, 0),
+            [
+                Binding::Key(KeyCode::I),
+                Binding::GamepadButton(GamepadButtonType::DPadUp),
+            ]
+        );
+        assert_eq!(
+            bindings.slot(Action::MoveForward, 1),
+            [
+                Binding::Key(KeyCode::Up),
+                Binding::GamepadAxis(GamepadAxisType::LeftStickY, AxisDirection::Positive),
+            ]
+        );
+    }
+
+    #[test]
+    fn rebinding_an_empty_slot_adds_a_binding() {
+        let mut bindings = InputBindings::default();
+
+        bindings.rebind(Action::Fire, 1, Binding::Key(KeyCode::Space));
+
+        assert_eq!(
+            bindings.slot(Action::Fire, 1),
+            [Binding::Key(KeyCode::Space)]
+        );
+        assert_eq!(bindings.get(Action::Fire).len(), 3);
+    }
+
+    #[test]
+    fn shared_bindings_are_conflicts() {
+        let mut bindings = InputBindings::default();
+
+        bindings.rebind(Action::Fire, 0, Binding::Key(KeyCode::W));
+
+        assert_eq!(
+            bindings.conflicts(),
+            [(
+                Binding::Key(KeyCode::W),
+                vec![Action::MoveForward, Action::Fire]
+            )]
+        );
+    }
+
+    #[test]
+    fn axis_directions_ignore_the_other_way() {
+        assert_eq!(AxisDirection::Positive.strength(0.8), 0.8);
+        assert_eq!(AxisDirection::Positive.strength(-0.8), 0.0);
+        assert_eq!(AxisDirection::Negative.strength(-0.8), 0.8);
+    }
+}
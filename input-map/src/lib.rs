@@ -0,0 +1,28 @@
+pub mod actions;
+pub mod bindings;
+mod systems;
+
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+
+use actions::*;
+use bindings::*;
+use systems::*;
+
+/// Turns keyboard, mouse and gamepad input into `ActionState`.
+pub struct InputMapPlugin;
+
+impl Plugin for InputMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputBindings>()
+            .init_resource::<ActionState>()
+            .init_resource::<ActiveGamepad>()
+            .add_startup_system(load_bindings)
+            .add_systems(
+                (assign_gamepad, update_action_state)
+                    .chain()
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(InputSystem),
+            );
+    }
+}
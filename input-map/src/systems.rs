@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+
+use super::actions::*;
+use super::bindings::*;
+
+/// Prefers the player's own bindings over the ones the game ships with.
+pub fn load_bindings(mut bindings: ResMut<InputBindings>) {
+    if let Some(path) = user_bindings_path().filter(|path| path.exists()) {
+        match InputBindings::load(&path) {
+            Ok(loaded) => {
+                *bindings = loaded;
+                return;
+            }
+            Err(error) => warn!("Could not load bindings from {}: {error}", path.display()),
+        }
+    }
+
+    match InputBindings::load(BINDINGS_PATH) {
+        Ok(loaded) => *bindings = loaded,
+        Err(error) => {
+            warn!("Could not load bindings from {BINDINGS_PATH}, using defaults: {error}")
+        }
+    }
+}
+
+/// Gives the player the first connected gamepad, and another one when it is unplugged.
+pub fn assign_gamepad(gamepads: Res<Gamepads>, mut active_gamepad: ResMut<ActiveGamepad>) {
+    if let Some(gamepad) = active_gamepad
+        .0
+        .filter(|gamepad| !gamepads.contains(*gamepad))
+    {
+        info!("Gamepad {} disconnected", gamepad.id);
+        active_gamepad.0 = None;
+    }
+
+    if active_gamepad.0.is_none() {
+        if let Some(gamepad) = gamepads.iter().next() {
+            info!("Gamepad {} assigned to the player", gamepad.id);
+            active_gamepad.0 = Some(gamepad);
+        }
+    }
+}
+
+pub fn update_action_state(
+    bindings: Res<InputBindings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    active_gamepad: Res<ActiveGamepad>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut action_state: ResMut<ActionState>,
+) {
+    let strength = |binding: &Binding| match *binding {
+        Binding::Key(key_code) => button_strength(keyboard_input.pressed(key_code)),
+        Binding::Mouse(button) => button_strength(mouse_input.pressed(button)),
+        Binding::GamepadButton(button_type) => {
+            button_strength(active_gamepad.0.is_some_and(|gamepad| {
+                gamepad_buttons.pressed(GamepadButton::new(gamepad, button_type))
+            }))
+        }
+        Binding::GamepadAxis(axis_type, direction) => active_gamepad
+            .0
+            .and_then(|gamepad| gamepad_axes.get(GamepadAxis::new(gamepad, axis_type)))
+            .map_or(0.0, |value| direction.strength(value)),
+    };
+
+    action_state.update(
+        bindings
+            .iter()
+            .map(|(action, binding)| (action, strength(binding))),
+    );
+}
+
+fn button_strength(pressed: bool) -> f32 {
+    if pressed {
+        1.0
+    } else {
+        0.0
+    }
+}
@@ -11,12 +11,13 @@ impl Plugin for PhysicsPlugin {
             .insert_resource(RapierConfiguration {
                 gravity: Vec2::new(0., 0.),
                 ..default()
-            })
-            .add_startup_system(setup_physics);
+            });
     }
 }
 
-fn setup_physics(mut commands: Commands) {
+/// A ground collider and a bouncing ball to try the physics out on. Games bring
+/// their own level instead.
+pub fn setup_physics(mut commands: Commands) {
     /* Create the ground. */
     commands.spawn((
         Name::new("Ground"),
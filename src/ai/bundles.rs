@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use big_brain::{actions::ConcurrentlyBuilder, prelude::*};
+
+use super::components::*;
+
+#[derive(Bundle)]
+pub struct AiTankBundle {
+    pub awareness: Awareness,
+    pub thinker: ThinkerBuilder,
+}
+
+impl AiTankBundle {
+    /// An AI driver that patrols around `home` until it spots or is shot by an enemy.
+    pub fn new(home: Vec2) -> Self {
+        Self {
+            awareness: Awareness::default(),
+            thinker: Thinker::build()
+                .label("TankThinker")
+                .picker(Highest)
+                .when(LowHealth { threshold: 0.3 }, Retreat { distance: 800.0 })
+                .when(TargetVisible, engage())
+                .when(UnderFire, engage())
+                .otherwise(Patrol {
+                    center: home,
+                    radius: 200.0,
+                    route: Vec::new(),
+                }),
+        }
+    }
+}
+
+fn engage() -> ConcurrentlyBuilder {
+    Concurrently::build()
+        .label("Engage")
+        .push(ChaseTarget {
+            range: 250.0,
+            route: Vec::new(),
+        })
+        .push(AimAndFire { tolerance: 0.05 })
+}
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use big_brain::prelude::*;
+
+/// What an AI-driven hull currently knows about its surroundings.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Component, Reflect, FromReflect, InspectorOptions,
+)]
+#[reflect(Component, PartialEq)]
+pub struct Awareness {
+    /// Closest enemy hull spotted by our team.
+    pub target: Option<Entity>,
+    /// The hull that damaged us last.
+    pub attacker: Option<Entity>,
+    /// Where the enemy to fight is, as far as our team knows.
+    pub enemy_position: Option<Vec2>,
+    /// Damage taken recently. Decays over time.
+    #[inspector(min = 0.0, max = 1000.0)]
+    pub threat: f32,
+}
+
+/// Scores 1 while an enemy hull is in sight.
+#[derive(Clone, Component, Debug, ScorerBuilder)]
+pub struct TargetVisible;
+
+/// Scores 1 once health drops below `threshold` of its maximum.
+#[derive(Clone, Component, Debug, ScorerBuilder)]
+pub struct LowHealth {
+    pub threshold: f32,
+}
+
+/// Scores how much damage was taken recently.
+#[derive(Clone, Component, Debug, ScorerBuilder)]
+pub struct UnderFire;
+
+/// Drives between random points within `radius` of `center`.
+#[derive(Clone, Component, Debug, ActionBuilder)]
+pub struct Patrol {
+    pub center: Vec2,
+    pub radius: f32,
+    /// Waypoints left to the current destination.
+    pub route: Vec<Vec2>,
+}
+
+/// Drives towards the enemy and holds position `range` away from it.
+#[derive(Clone, Component, Debug, ActionBuilder)]
+pub struct ChaseTarget {
+    pub range: f32,
+    /// Waypoints left to where the enemy was when the route was planned.
+    pub route: Vec<Vec2>,
+}
+
+/// Points the turrets at the enemy and fires once they are within `tolerance` radians.
+#[derive(Clone, Component, Debug, ActionBuilder)]
+pub struct AimAndFire {
+    pub tolerance: f32,
+}
+
+/// Drives away from the enemy until `distance` away from it.
+#[derive(Clone, Component, Debug, ActionBuilder)]
+pub struct Retreat {
+    pub distance: f32,
+}
@@ -0,0 +1,57 @@
+pub mod bundles;
+pub mod components;
+mod systems;
+
+use bevy::prelude::*;
+use big_brain::prelude::*;
+
+use components::*;
+use systems::*;
+
+use crate::{AppState, GameplaySet, SimulationState};
+
+pub struct AiPlugin;
+
+impl Plugin for AiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(BigBrainPlugin).register_type::<Awareness>();
+
+        // Big-brain's sets are bound to their own base sets, so they are paused with
+        // run conditions rather than by joining `GameplaySet`.
+        for set in [
+            BigBrainSet::Scorers,
+            BigBrainSet::Thinkers,
+            BigBrainSet::Actions,
+        ] {
+            app.configure_set(
+                set.run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
+            );
+        }
+
+        app.add_systems(
+            (track_attackers, perceive_targets)
+                .chain()
+                .in_base_set(CoreSet::First)
+                .before(BigBrainSet::Scorers)
+                .in_set(GameplaySet),
+        )
+        .add_systems(
+            (
+                target_visible_scorer_system,
+                low_health_scorer_system,
+                under_fire_scorer_system,
+            )
+                .in_set(BigBrainSet::Scorers),
+        )
+        .add_systems(
+            (
+                patrol_action_system,
+                chase_target_action_system,
+                aim_and_fire_action_system,
+                retreat_action_system,
+            )
+                .in_set(BigBrainSet::Actions),
+        );
+    }
+}
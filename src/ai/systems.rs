@@ -0,0 +1,376 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use bevy::prelude::*;
+use big_brain::prelude::*;
+use rand::prelude::*;
+
+use super::components::*;
+use crate::combat::{components::Health, events::DamageEvent};
+use crate::gun::{
+    components::{Gun, Turret},
+    events::FireGun,
+};
+use crate::navigation::grid::NavGrid;
+use crate::spotting::components::Spotted;
+use crate::tank::components::{Tank, Team};
+use crate::tracks::components::Tracks;
+
+/// Damage per second forgotten by `Awareness::threat`.
+const THREAT_DECAY: f32 = 10.0;
+/// Recent damage at which `UnderFire` scores its highest.
+const UNDER_FIRE_DAMAGE: f32 = 30.0;
+/// `UnderFire` never outscores an enemy that is actually in sight.
+const UNDER_FIRE_MAX_SCORE: f32 = 0.8;
+const ARRIVE_DISTANCE: f32 = 30.0;
+/// Clearance tanks keep from static obstacles when planning routes.
+const AGENT_RADIUS: f32 = 30.0;
+/// How far the enemy may move from the end of a route before it is planned again.
+const REPLAN_DISTANCE: f32 = 100.0;
+
+/// Picks the closest enemy hull the team has spotted. Enemies that are not
+/// spotted are only known through the position their last hit gave away.
+pub fn perceive_targets(
+    mut ai_query: Query<(&mut Awareness, &Transform, &Team)>,
+    hull_query: Query<(Entity, &Transform, &Team, &Spotted), With<Tank>>,
+) {
+    for (mut awareness, transform, team) in ai_query.iter_mut() {
+        let position = transform.translation.truncate();
+
+        let target = hull_query
+            .iter()
+            .filter(|(_, _, other_team, spotted)| {
+                *other_team != team && spotted.by_team.contains(team)
+            })
+            .map(|(other, other_transform, _, _)| (other, other_transform.translation.truncate()))
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            });
+
+        if awareness.threat <= 0.0
+            || awareness
+                .attacker
+                .is_some_and(|attacker| !hull_query.contains(attacker))
+        {
+            awareness.attacker = None;
+        }
+
+        awareness.target = target.map(|(other, _)| other);
+        if let Some((_, other_position)) = target {
+            awareness.enemy_position = Some(other_position);
+        } else if awareness.attacker.is_none() {
+            awareness.enemy_position = None;
+        }
+    }
+}
+
+pub fn track_attackers(
+    time: Res<Time>,
+    mut damage_events: EventReader<DamageEvent>,
+    mut awareness_query: Query<&mut Awareness>,
+    source_query: Query<&Transform, With<Tank>>,
+) {
+    for mut awareness in awareness_query.iter_mut() {
+        awareness.threat = (awareness.threat - THREAT_DECAY * time.delta_seconds()).max(0.0);
+    }
+
+    for event in damage_events.iter() {
+        if let Ok(mut awareness) = awareness_query.get_mut(event.target) {
+            awareness.threat += event.amount;
+
+            if let Some(source) = event.source.filter(|source| *source != event.target) {
+                awareness.attacker = Some(source);
+
+                // Getting hit gives away where the shot came from.
+                if let Ok(source_transform) = source_query.get(source) {
+                    awareness.enemy_position = Some(source_transform.translation.truncate());
+                }
+            }
+        }
+    }
+}
+
+pub fn target_visible_scorer_system(
+    awareness_query: Query<&Awareness>,
+    mut query: Query<(&Actor, &mut Score), With<TargetVisible>>,
+) {
+    for (Actor(actor), mut score) in &mut query {
+        if let Ok(awareness) = awareness_query.get(*actor) {
+            score.set(if awareness.target.is_some() { 1.0 } else { 0.0 });
+        }
+    }
+}
+
+pub fn low_health_scorer_system(
+    health_query: Query<&Health>,
+    mut query: Query<(&Actor, &mut Score, &LowHealth)>,
+) {
+    for (Actor(actor), mut score, low_health) in &mut query {
+        if let Ok(health) = health_query.get(*actor) {
+            let fraction = health.current / health.max;
+            score.set(if fraction < low_health.threshold {
+                1.0
+            } else {
+                0.0
+            });
+        }
+    }
+}
+
+pub fn under_fire_scorer_system(
+    awareness_query: Query<&Awareness>,
+    mut query: Query<(&Actor, &mut Score), With<UnderFire>>,
+) {
+    for (Actor(actor), mut score) in &mut query {
+        if let Ok(awareness) = awareness_query.get(*actor) {
+            let threat = (awareness.threat / UNDER_FIRE_DAMAGE).min(1.0);
+            score.set(if awareness.attacker.is_some() {
+                threat * UNDER_FIRE_MAX_SCORE
+            } else {
+                0.0
+            });
+        }
+    }
+}
+
+pub fn patrol_action_system(
+    nav_grid: Res<NavGrid>,
+    mut hull_query: Query<(&Transform, &mut Tracks)>,
+    mut action_query: Query<(&Actor, &mut ActionState, &mut Patrol)>,
+) {
+    for (Actor(actor), mut state, mut patrol) in &mut action_query {
+        let Ok((transform, mut tracks)) = hull_query.get_mut(*actor) else {
+            continue;
+        };
+
+        match *state {
+            ActionState::Requested => {
+                patrol.route.clear();
+                *state = ActionState::Executing;
+            }
+            ActionState::Executing => {
+                if patrol.route.is_empty() {
+                    let angle = random::<f32>() * 2.0 * PI;
+                    let destination =
+                        patrol.center + Vec2::from_angle(angle) * random::<f32>() * patrol.radius;
+
+                    // Unreachable destinations are skipped and a new one is picked next tick.
+                    patrol.route = nav_grid
+                        .find_path(transform.translation.truncate(), destination, AGENT_RADIUS)
+                        .unwrap_or_default();
+                }
+
+                follow_route(&mut tracks, transform, &mut patrol.route, ARRIVE_DISTANCE);
+            }
+            ActionState::Cancelled => {
+                stop(&mut tracks);
+                *state = ActionState::Failure;
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn chase_target_action_system(
+    nav_grid: Res<NavGrid>,
+    mut hull_query: Query<(&Transform, &mut Tracks, &Awareness)>,
+    mut action_query: Query<(&Actor, &mut ActionState, &mut ChaseTarget)>,
+) {
+    for (Actor(actor), mut state, mut chase) in &mut action_query {
+        let Ok((transform, mut tracks, awareness)) = hull_query.get_mut(*actor) else {
+            continue;
+        };
+
+        match *state {
+            ActionState::Requested => {
+                chase.route.clear();
+                *state = ActionState::Executing;
+            }
+            ActionState::Executing => {
+                let Some(target) = awareness.enemy_position else {
+                    stop(&mut tracks);
+                    *state = ActionState::Failure;
+                    continue;
+                };
+
+                let position = transform.translation.truncate();
+                if position.distance(target) <= chase.range
+                    && nav_grid.is_line_clear(position, target, AGENT_RADIUS)
+                {
+                    chase.route.clear();
+                    drive_towards(&mut tracks, transform, target, chase.range);
+                    continue;
+                }
+
+                if chase
+                    .route
+                    .last()
+                    .is_none_or(|goal| goal.distance(target) > REPLAN_DISTANCE)
+                {
+                    // Without a path, e.g. when the enemy hugs a wall, drive straight at it.
+                    chase.route = nav_grid
+                        .find_path(position, target, AGENT_RADIUS)
+                        .unwrap_or_else(|| vec![target]);
+                }
+
+                let range = chase.range;
+                follow_route(&mut tracks, transform, &mut chase.route, range);
+            }
+            ActionState::Cancelled => {
+                stop(&mut tracks);
+                *state = ActionState::Failure;
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn aim_and_fire_action_system(
+    mut fire_events: EventWriter<FireGun>,
+    hull_query: Query<(&Awareness, &Children)>,
+    mut gun_query: Query<&mut Turret, With<Gun>>,
+    mut action_query: Query<(&Actor, &mut ActionState, &AimAndFire)>,
+) {
+    for (Actor(actor), mut state, aim) in &mut action_query {
+        let Ok((awareness, children)) = hull_query.get(*actor) else {
+            continue;
+        };
+
+        match *state {
+            ActionState::Requested => {
+                *state = ActionState::Executing;
+            }
+            ActionState::Executing => {
+                let target = awareness.enemy_position;
+
+                for &child in children.iter() {
+                    if let Ok(mut turret) = gun_query.get_mut(child) {
+                        turret.target = target;
+
+                        // Only shoot at what can actually be seen.
+                        if awareness.target.is_some() && turret.is_on_target(aim.tolerance) {
+                            fire_events.send(FireGun { gun: child });
+                        }
+                    }
+                }
+
+                if target.is_none() {
+                    *state = ActionState::Failure;
+                }
+            }
+            ActionState::Cancelled => {
+                for &child in children.iter() {
+                    if let Ok(mut turret) = gun_query.get_mut(child) {
+                        turret.target = None;
+                    }
+                }
+                *state = ActionState::Failure;
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn retreat_action_system(
+    mut hull_query: Query<(&Transform, &mut Tracks, &Awareness)>,
+    mut action_query: Query<(&Actor, &mut ActionState, &Retreat)>,
+) {
+    for (Actor(actor), mut state, retreat) in &mut action_query {
+        let Ok((transform, mut tracks, awareness)) = hull_query.get_mut(*actor) else {
+            continue;
+        };
+
+        match *state {
+            ActionState::Requested => {
+                *state = ActionState::Executing;
+            }
+            ActionState::Executing => {
+                let Some(enemy_position) = awareness.enemy_position else {
+                    stop(&mut tracks);
+                    *state = ActionState::Success;
+                    continue;
+                };
+
+                let position = transform.translation.truncate();
+                let away = (position - enemy_position).normalize_or_zero();
+
+                if position.distance(enemy_position) >= retreat.distance {
+                    stop(&mut tracks);
+                    *state = ActionState::Success;
+                } else {
+                    drive_towards(
+                        &mut tracks,
+                        transform,
+                        enemy_position + away * retreat.distance,
+                        ARRIVE_DISTANCE,
+                    );
+                }
+            }
+            ActionState::Cancelled => {
+                stop(&mut tracks);
+                *state = ActionState::Failure;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Steers the hull towards `destination`, stopping `stop_distance` short of it.
+/// Returns the remaining distance.
+fn drive_towards(
+    tracks: &mut Tracks,
+    transform: &Transform,
+    destination: Vec2,
+    stop_distance: f32,
+) -> f32 {
+    let offset = destination - transform.translation.truncate();
+    let distance = offset.length();
+    let forward = (transform.rotation * Vec3::X).truncate();
+    let angle = if distance > f32::EPSILON {
+        forward.angle_between(offset)
+    } else {
+        0.0
+    };
+
+    tracks.steer = (angle / FRAC_PI_4).clamp(-1.0, 1.0);
+    tracks.throttle = if distance <= stop_distance {
+        0.0
+    } else if angle.abs() > FRAC_PI_2 {
+        // Turn on the spot before driving off in the wrong direction.
+        0.0
+    } else {
+        1.0 - angle.abs() / FRAC_PI_2
+    };
+
+    distance
+}
+
+/// Drives along `route`, dropping waypoints as they are passed and stopping
+/// `stop_distance` short of the last one. The route is empty once it is finished.
+fn follow_route(
+    tracks: &mut Tracks,
+    transform: &Transform,
+    route: &mut Vec<Vec2>,
+    stop_distance: f32,
+) {
+    let position = transform.translation.truncate();
+    while route.len() > 1 && position.distance(route[0]) <= ARRIVE_DISTANCE {
+        route.remove(0);
+    }
+
+    let Some(&waypoint) = route.first() else {
+        stop(tracks);
+        return;
+    };
+
+    if route.len() > 1 {
+        drive_towards(tracks, transform, waypoint, 0.0);
+    } else if drive_towards(tracks, transform, waypoint, stop_distance) <= stop_distance {
+        route.clear();
+    }
+}
+
+fn stop(tracks: &mut Tracks) {
+    tracks.throttle = 0.0;
+    tracks.steer = 0.0;
+}
@@ -0,0 +1,139 @@
+use bevy::math::Vec2;
+
+use super::components::Armor;
+
+/// Shells hitting flatter than this (measured from the surface normal) always ricochet.
+pub const RICOCHET_ANGLE: f32 = 70.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArmorFace {
+    Front,
+    Side,
+    Rear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HitOutcome {
+    Penetrated { effective_armor: f32 },
+    NotPenetrated { effective_armor: f32 },
+    Ricochet { direction: Vec2 },
+}
+
+impl Armor {
+    pub fn face(&self, face: ArmorFace) -> f32 {
+        match face {
+            ArmorFace::Front => self.front,
+            ArmorFace::Side => self.side,
+            ArmorFace::Rear => self.rear,
+        }
+    }
+}
+
+/// Which face of a hull facing `hull_forward` has the outward normal `normal`.
+pub fn armor_face(hull_forward: Vec2, normal: Vec2) -> ArmorFace {
+    let alignment = hull_forward
+        .normalize_or_zero()
+        .dot(normal.normalize_or_zero());
+    let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+
+    if alignment > diagonal {
+        ArmorFace::Front
+    } else if alignment < -diagonal {
+        ArmorFace::Rear
+    } else {
+        ArmorFace::Side
+    }
+}
+
+/// Decides what a shell travelling along `shell_velocity` with `penetration` does
+/// when it hits the surface with outward `normal` of a hull facing `hull_forward`.
+pub fn resolve_hit(
+    armor: &Armor,
+    hull_forward: Vec2,
+    normal: Vec2,
+    shell_velocity: Vec2,
+    penetration: f32,
+) -> HitOutcome {
+    let normal = normal.normalize_or_zero();
+    let direction = shell_velocity.normalize_or_zero();
+    let cos_impact = (-direction).dot(normal).clamp(0.0, 1.0);
+
+    if cos_impact <= RICOCHET_ANGLE.to_radians().cos() {
+        return HitOutcome::Ricochet {
+            direction: direction - 2.0 * direction.dot(normal) * normal,
+        };
+    }
+
+    let effective_armor = armor.face(armor_face(hull_forward, normal)) / cos_impact;
+
+    if penetration >= effective_armor {
+        HitOutcome::Penetrated { effective_armor }
+    } else {
+        HitOutcome::NotPenetrated { effective_armor }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARMOR: Armor = Armor {
+        front: 100.0,
+        side: 60.0,
+        rear: 30.0,
+    };
+
+    #[test]
+    fn picks_face_from_normal() {
+        assert_eq!(armor_face(Vec2::X, Vec2::X), ArmorFace::Front);
+        assert_eq!(armor_face(Vec2::X, Vec2::Y), ArmorFace::Side);
+        assert_eq!(armor_face(Vec2::X, Vec2::NEG_X), ArmorFace::Rear);
+        assert_eq!(armor_face(Vec2::Y, Vec2::NEG_X), ArmorFace::Side);
+    }
+
+    #[test]
+    fn head_on_hit_uses_face_armor() {
+        let outcome = resolve_hit(&ARMOR, Vec2::X, Vec2::X, Vec2::NEG_X * 900.0, 120.0);
+        assert_eq!(
+            outcome,
+            HitOutcome::Penetrated {
+                effective_armor: 100.0
+            }
+        );
+
+        let outcome = resolve_hit(&ARMOR, Vec2::X, Vec2::X, Vec2::NEG_X * 900.0, 90.0);
+        assert_eq!(
+            outcome,
+            HitOutcome::NotPenetrated {
+                effective_armor: 100.0
+            }
+        );
+    }
+
+    #[test]
+    fn angled_hit_increases_effective_armor() {
+        let velocity = Vec2::new(-1.0, -1.0);
+        let outcome = resolve_hit(&ARMOR, Vec2::NEG_X, Vec2::Y, velocity, 50.0);
+
+        match outcome {
+            HitOutcome::NotPenetrated { effective_armor } => {
+                assert!((effective_armor - 60.0 * std::f32::consts::SQRT_2).abs() < 1e-3);
+            }
+            outcome => panic!("unexpected outcome {outcome:?}"),
+        }
+    }
+
+    #[test]
+    fn glancing_hit_ricochets() {
+        let velocity = Vec2::new(-1.0, -0.2);
+        let outcome = resolve_hit(&ARMOR, Vec2::X, Vec2::Y, velocity, 1000.0);
+
+        match outcome {
+            HitOutcome::Ricochet { direction } => {
+                let expected = Vec2::new(-1.0, 0.2).normalize();
+                assert!(direction.abs_diff_eq(expected, 1e-5));
+            }
+            outcome => panic!("unexpected outcome {outcome:?}"),
+        }
+    }
+}
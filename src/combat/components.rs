@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, PartialEq, Component, Reflect, FromReflect, InspectorOptions)]
+#[reflect(Component, PartialEq)]
+pub struct Health {
+    #[inspector(min = 0.0, max = 10000.0)]
+    pub current: f32,
+    #[inspector(min = 0.0, max = 10000.0)]
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.0
+    }
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new(100.0)
+    }
+}
+
+/// Armor thickness of each hull face, compared against shell penetration.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    InspectorOptions,
+    Deserialize,
+)]
+#[reflect(Component, PartialEq)]
+pub struct Armor {
+    #[inspector(min = 0.0, max = 1000.0)]
+    pub front: f32,
+    #[inspector(min = 0.0, max = 1000.0)]
+    pub side: f32,
+    #[inspector(min = 0.0, max = 1000.0)]
+    pub rear: f32,
+}
+
+#[derive(Component)]
+pub struct Wreck;
+
+/// A level obstacle that takes damage like a tank. When its `Health` runs out it
+/// breaks apart instead of leaving a wreck.
+#[derive(Copy, Clone, Debug, PartialEq, Component, Serialize, Deserialize)]
+pub struct Destructible {
+    pub health: f32,
+    /// Shatter into loose debris. Otherwise the obstacle simply disappears.
+    #[serde(default)]
+    pub fracture: bool,
+}
+
+#[derive(Component)]
+pub struct Debris;
+
+/// How hard colliders must hit each other before they take damage.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct CollisionDamage {
+    /// Contact force magnitude that is absorbed without damage.
+    pub force_threshold: f32,
+    pub damage_per_force: f32,
+}
+
+impl Default for CollisionDamage {
+    fn default() -> Self {
+        Self {
+            force_threshold: 200.0,
+            damage_per_force: 0.05,
+        }
+    }
+}
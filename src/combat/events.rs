@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+/// Damage dealt to `target` after armor has been resolved.
+#[derive(Debug, Clone, Copy)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: f32,
+    /// The hull responsible for the damage, if any.
+    pub source: Option<Entity>,
+}
+
+/// `entity` ran out of health and is about to be replaced by a wreck.
+#[derive(Debug, Clone, Copy)]
+pub struct DestroyedEvent {
+    pub entity: Entity,
+    pub source: Option<Entity>,
+}
@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use lyon::path::Path;
+use lyon::tessellation::{BuffersBuilder, FillOptions, FillTessellator, FillVertex, VertexBuffers};
+
+/// Splits the filled area of `path` into about `pieces` triangles.
+///
+/// The path is tessellated and the largest triangle is cut in half across its longest
+/// edge until there are enough pieces, so the debris ends up roughly the same size.
+pub fn fracture(path: &Path, pieces: usize) -> Vec<[Vec2; 3]> {
+    let mut buffers: VertexBuffers<Vec2, u32> = VertexBuffers::new();
+    let result = FillTessellator::new().tessellate_path(
+        path,
+        &FillOptions::default(),
+        &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| {
+            Vec2::new(vertex.position().x, vertex.position().y)
+        }),
+    );
+
+    if result.is_err() {
+        return Vec::new();
+    }
+
+    let mut triangles: Vec<[Vec2; 3]> = buffers
+        .indices
+        .chunks_exact(3)
+        .map(|indices| indices_to_triangle(&buffers.vertices, indices))
+        .filter(|triangle| area(triangle) > f32::EPSILON)
+        .collect();
+
+    while triangles.len() < pieces {
+        let Some((largest, _)) = triangles
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| area(a).total_cmp(&area(b)))
+        else {
+            break;
+        };
+
+        let [first, second] = split(triangles.swap_remove(largest));
+        triangles.push(first);
+        triangles.push(second);
+    }
+
+    triangles
+}
+
+fn indices_to_triangle(vertices: &[Vec2], indices: &[u32]) -> [Vec2; 3] {
+    [0, 1, 2].map(|corner| vertices[indices[corner] as usize])
+}
+
+fn area([a, b, c]: &[Vec2; 3]) -> f32 {
+    (*b - *a).perp_dot(*c - *a).abs() / 2.0
+}
+
+/// Cuts from the middle of the longest edge to the opposite corner.
+fn split([a, b, c]: [Vec2; 3]) -> [[Vec2; 3]; 2] {
+    let edges = [(a, b, c), (b, c, a), (c, a, b)];
+    let (from, to, opposite) = edges
+        .into_iter()
+        .max_by(|(a1, b1, _), (a2, b2, _)| a1.distance(*b1).total_cmp(&a2.distance(*b2)))
+        .unwrap();
+    let middle = (from + to) / 2.0;
+
+    [[from, middle, opposite], [middle, to, opposite]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lyon::path::{math::point, Polygon};
+
+    fn square(size: f32) -> Path {
+        let mut builder = Path::builder();
+        builder.add_polygon(Polygon {
+            points: &[
+                point(0.0, 0.0),
+                point(size, 0.0),
+                point(size, size),
+                point(0.0, size),
+            ],
+            closed: true,
+        });
+        builder.build()
+    }
+
+    #[test]
+    fn pieces_cover_the_whole_shape() {
+        let triangles = fracture(&square(100.0), 12);
+        let total: f32 = triangles.iter().map(area).sum();
+
+        assert_eq!(triangles.len(), 12);
+        assert!((total - 10_000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn pieces_are_about_the_same_size() {
+        let triangles = fracture(&square(100.0), 16);
+        let largest = triangles.iter().map(area).fold(0.0, f32::max);
+        let smallest = triangles.iter().map(area).fold(f32::MAX, f32::min);
+
+        assert!(largest <= smallest * 2.0 + 1.0);
+    }
+}
@@ -0,0 +1,31 @@
+pub mod armor;
+pub mod components;
+pub mod events;
+mod fracture;
+mod systems;
+
+use bevy::prelude::*;
+
+use components::*;
+use events::*;
+use systems::*;
+
+use crate::GameplaySet;
+
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Health>()
+            .register_type::<Armor>()
+            .init_resource::<CollisionDamage>()
+            .add_event::<DamageEvent>()
+            .add_event::<DestroyedEvent>()
+            .add_system(enable_collision_damage.in_set(GameplaySet))
+            .add_system(shell_hit_damage.before(apply_damage).in_set(GameplaySet))
+            .add_system(hitscan_hit_damage.before(apply_damage).in_set(GameplaySet))
+            .add_system(collision_damage.before(apply_damage).in_set(GameplaySet))
+            .add_system(apply_damage.in_set(GameplaySet))
+            .add_system(destroy_entities.after(apply_damage).in_set(GameplaySet));
+    }
+}
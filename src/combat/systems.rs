@@ -0,0 +1,288 @@
+use bevy::{prelude::*, utils::HashSet};
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::armor::*;
+use super::components::*;
+use super::events::*;
+use super::fracture::*;
+use crate::gun::{
+    bundles::ShellBundle,
+    components::ShellType,
+    events::{HitscanHit, ShellHit},
+};
+use crate::GameSessionEntity;
+
+const RICOCHET_SPEED_FACTOR: f32 = 0.7;
+const RICOCHET_PENETRATION_FACTOR: f32 = 0.5;
+const DEBRIS_PIECES: usize = 12;
+/// How fast debris flies away from the centre of the destroyed obstacle.
+const DEBRIS_SPEED: f32 = 150.0;
+
+type DestroyedItem<'a> = (
+    &'a GlobalTransform,
+    Option<&'a Path>,
+    Option<&'a Collider>,
+    Option<&'a Destructible>,
+    Option<&'a Fill>,
+);
+
+pub fn shell_hit_damage(
+    mut commands: Commands,
+    mut hit_events: EventReader<ShellHit>,
+    mut damage_events: EventWriter<DamageEvent>,
+    armor_query: Query<(&Armor, &GlobalTransform)>,
+) {
+    for hit in hit_events.iter() {
+        let outcome = match armor_query.get(hit.target) {
+            Ok((armor, global_transform)) => resolve_hit(
+                armor,
+                hull_forward(global_transform),
+                hit.normal,
+                hit.velocity,
+                hit.shell.penetration,
+            ),
+            Err(_) => HitOutcome::Penetrated {
+                effective_armor: 0.0,
+            },
+        };
+
+        match outcome {
+            HitOutcome::Penetrated { .. } => {
+                damage_events.send(DamageEvent {
+                    target: hit.target,
+                    amount: hit.shell.damage,
+                    source: Some(hit.owner),
+                });
+            }
+            HitOutcome::NotPenetrated { .. } => {}
+            HitOutcome::Ricochet { direction } => {
+                let shell = ShellType {
+                    penetration: hit.shell.penetration * RICOCHET_PENETRATION_FACTOR,
+                    ..hit.shell
+                };
+                let speed = hit.velocity.length() * RICOCHET_SPEED_FACTOR;
+
+                commands.spawn((
+                    ShellBundle::new(
+                        shell,
+                        hit.owner,
+                        hit.position + hit.normal * shell.radius * 2.0,
+                        direction * speed,
+                    ),
+                    GameSessionEntity,
+                ));
+            }
+        }
+    }
+}
+
+/// Hitscan hits are resolved against armor like shells, but glancing ones simply stop.
+pub fn hitscan_hit_damage(
+    mut hit_events: EventReader<HitscanHit>,
+    mut damage_events: EventWriter<DamageEvent>,
+    armor_query: Query<(&Armor, &GlobalTransform)>,
+) {
+    for hit in hit_events.iter() {
+        let penetrated = match armor_query.get(hit.target) {
+            Ok((armor, global_transform)) => matches!(
+                resolve_hit(
+                    armor,
+                    hull_forward(global_transform),
+                    hit.normal,
+                    hit.direction,
+                    hit.penetration,
+                ),
+                HitOutcome::Penetrated { .. }
+            ),
+            Err(_) => true,
+        };
+
+        if penetrated {
+            damage_events.send(DamageEvent {
+                target: hit.target,
+                amount: hit.damage,
+                source: Some(hit.owner),
+            });
+        }
+    }
+}
+
+fn hull_forward(global_transform: &GlobalTransform) -> Vec2 {
+    let (_, rotation, _) = global_transform.to_scale_rotation_translation();
+    (rotation * Vec3::X).truncate()
+}
+
+/// Anything that can take damage reports contact forces to rapier.
+pub fn enable_collision_damage(
+    mut commands: Commands,
+    collision_damage: Res<CollisionDamage>,
+    health_query: Query<(Entity, Option<&ActiveEvents>), Added<Health>>,
+) {
+    for (entity, active_events) in health_query.iter() {
+        let active_events = active_events.copied().unwrap_or_default();
+
+        commands.entity(entity).insert((
+            active_events | ActiveEvents::CONTACT_FORCE_EVENTS,
+            ContactForceEventThreshold(collision_damage.force_threshold),
+        ));
+    }
+}
+
+pub fn collision_damage(
+    collision_damage: Res<CollisionDamage>,
+    mut contact_force_events: EventReader<ContactForceEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    health_query: Query<(), With<Health>>,
+) {
+    for event in contact_force_events.iter() {
+        let excess = event.total_force_magnitude - collision_damage.force_threshold;
+
+        if excess <= 0.0 {
+            continue;
+        }
+
+        for (target, source) in [
+            (event.collider1, event.collider2),
+            (event.collider2, event.collider1),
+        ] {
+            if health_query.contains(target) {
+                damage_events.send(DamageEvent {
+                    target,
+                    amount: excess * collision_damage.damage_per_force,
+                    source: Some(source),
+                });
+            }
+        }
+    }
+}
+
+pub fn apply_damage(
+    mut damage_events: EventReader<DamageEvent>,
+    mut destroyed_events: EventWriter<DestroyedEvent>,
+    mut health_query: Query<&mut Health>,
+) {
+    for event in damage_events.iter() {
+        if let Ok(mut health) = health_query.get_mut(event.target) {
+            if health.is_dead() {
+                continue;
+            }
+
+            health.current -= event.amount;
+
+            if health.is_dead() {
+                destroyed_events.send(DestroyedEvent {
+                    entity: event.target,
+                    source: event.source,
+                });
+            }
+        }
+    }
+}
+
+pub fn destroy_entities(
+    mut commands: Commands,
+    mut destroyed_events: EventReader<DestroyedEvent>,
+    destroyed_query: Query<DestroyedItem>,
+    joint_query: Query<(Entity, &ImpulseJoint)>,
+) {
+    let mut destroyed = HashSet::new();
+
+    for event in destroyed_events.iter() {
+        if !destroyed.insert(event.entity) {
+            continue;
+        }
+
+        if let Ok((global_transform, path, collider, destructible, fill)) =
+            destroyed_query.get(event.entity)
+        {
+            info!("{:?} destroyed by {:?}", event.entity, event.source);
+
+            // Walls are children of the level, so the pieces are placed in world space.
+            let transform = global_transform.compute_transform();
+            match (path, destructible) {
+                (Some(path), None) => spawn_wreck(&mut commands, transform, path, collider),
+                (Some(path), Some(destructible)) if destructible.fracture => {
+                    let color = fill.map_or(Color::GRAY, |fill| fill.color);
+                    spawn_debris(&mut commands, transform, path, color);
+                }
+                _ => {}
+            }
+
+            // Controllers such as `PlayerPull` are separate bodies jointed to the hull.
+            for (joint_entity, joint) in joint_query.iter() {
+                if joint.parent == event.entity {
+                    commands.entity(joint_entity).despawn_recursive();
+                }
+            }
+
+            commands.entity(event.entity).despawn_recursive();
+        }
+    }
+}
+
+fn spawn_wreck(
+    commands: &mut Commands,
+    transform: Transform,
+    path: &Path,
+    collider: Option<&Collider>,
+) {
+    let mut wreck = commands.spawn((
+        ShapeBundle {
+            path: Path(path.0.clone()),
+            transform,
+            ..default()
+        },
+        Fill::color(Color::hex("4d4d4d").unwrap()),
+        Stroke::new(Color::hex("191919").unwrap(), 2.0),
+        Name::new("Wreck"),
+        Wreck,
+        GameSessionEntity,
+    ));
+
+    if let Some(collider) = collider {
+        wreck.insert((
+            RigidBody::Dynamic,
+            collider.clone(),
+            Damping {
+                linear_damping: 100.0,
+                angular_damping: 100.0,
+            },
+        ));
+    }
+}
+
+/// Loose pieces of a fractured obstacle, flying away from its centre.
+fn spawn_debris(commands: &mut Commands, transform: Transform, path: &Path, color: Color) {
+    for triangle in fracture(&path.0, DEBRIS_PIECES) {
+        let center = (triangle[0] + triangle[1] + triangle[2]) / 3.0;
+        let points = triangle.map(|point| point - center);
+        let piece_transform = transform * Transform::from_translation(center.extend(0.0));
+        let direction = (transform.rotation * center.extend(0.0))
+            .truncate()
+            .normalize_or_zero();
+
+        commands.spawn((
+            ShapeBundle {
+                path: GeometryBuilder::build_as(&shapes::Polygon {
+                    points: points.to_vec(),
+                    closed: true,
+                }),
+                transform: piece_transform,
+                ..default()
+            },
+            Fill::color(color),
+            Stroke::new(Color::hex("191919").unwrap(), 1.0),
+            Name::new("Debris"),
+            Debris,
+            GameSessionEntity,
+            RigidBody::Dynamic,
+            Collider::triangle(points[0], points[1], points[2]),
+            Velocity::linear(direction * DEBRIS_SPEED),
+            Damping {
+                linear_damping: 3.0,
+                angular_damping: 3.0,
+            },
+        ));
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::{Component, Resource};
+use input_map::actions::Action;
+
+#[derive(Component)]
+pub struct ControlsMenu {}
+
+#[derive(Component)]
+pub struct ControlsStatus {}
+
+/// The action waiting for its new binding, if any, and which of its slots it goes in.
+#[derive(Resource, Default)]
+pub struct PendingRebind {
+    pub action: Option<Action>,
+    pub slot: usize,
+}
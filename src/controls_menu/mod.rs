@@ -0,0 +1,39 @@
+mod components;
+mod styles;
+mod systems;
+
+use systems::interactions::*;
+use systems::layout::*;
+
+use bevy::prelude::*;
+
+use crate::menu::systems::interact_with_menu_buttons;
+use crate::AppState;
+use components::PendingRebind;
+
+/// Lists every action and lets the player bind it to another key or button.
+pub struct ControlsMenuPlugin;
+
+impl Plugin for ControlsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingRebind>()
+            // OnEnter State Systems
+            .add_system(spawn_controls_menu.in_schedule(OnEnter(AppState::Controls)))
+            // Systems
+            .add_systems(
+                (
+                    // Runs first so the click that starts a rebind is not taken as the binding.
+                    capture_binding,
+                    handle_controls_menu_buttons,
+                    update_binding_labels,
+                    update_controls_status,
+                    suspend_focus_while_rebinding,
+                )
+                    .chain()
+                    .after(interact_with_menu_buttons)
+                    .in_set(OnUpdate(AppState::Controls)),
+            )
+            // OnExit State Systems
+            .add_system(despawn_controls_menu.in_schedule(OnExit(AppState::Controls)));
+    }
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+pub const BINDING_FONT_SIZE: f32 = 20.0;
+
+pub const CONFLICT_TEXT_COLOR: Color = Color::rgb(0.9, 0.3, 0.3);
+
+pub const CONTROLS_MENU_STYLE: Style = Style {
+    flex_direction: FlexDirection::Column,
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+    gap: Size::new(Val::Px(2.0), Val::Px(2.0)),
+    ..Style::DEFAULT
+};
+
+pub const BINDING_ROW_STYLE: Style = Style {
+    flex_direction: FlexDirection::Row,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(800.0), Val::Px(32.0)),
+    ..Style::DEFAULT
+};
+
+pub const ACTION_LABEL_STYLE: Style = Style {
+    size: Size::new(Val::Px(300.0), Val::Auto),
+    ..Style::DEFAULT
+};
+
+pub const BINDING_BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(250.0), Val::Px(32.0)),
+    ..Style::DEFAULT
+};
+
+pub const STATUS_STYLE: Style = Style {
+    margin: UiRect::new(Val::Px(0.0), Val::Px(0.0), Val::Px(8.0), Val::Px(8.0)),
+    ..Style::DEFAULT
+};
@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use input_map::bindings::*;
+
+use crate::controls_menu::components::*;
+use crate::controls_menu::styles::CONFLICT_TEXT_COLOR;
+use crate::menu::{
+    components::{MenuAction, MenuButton},
+    events::MenuButtonPressed,
+    systems::set_button_label,
+};
+use crate::ui_focus::components::UiFocus;
+use crate::AppState;
+
+/// How far a stick has to be pushed to be bound.
+const CAPTURE_AXIS_THRESHOLD: f32 = 0.5;
+const CAPTURE_AXES: [GamepadAxisType; 4] = [
+    GamepadAxisType::LeftStickX,
+    GamepadAxisType::LeftStickY,
+    GamepadAxisType::RightStickX,
+    GamepadAxisType::RightStickY,
+];
+
+/// Binds the first key, mouse button, or button or stick of the player's gamepad pressed
+/// while a rebind is pending and saves the bindings.
+pub fn capture_binding(
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    active_gamepad: Res<ActiveGamepad>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut pending_rebind: ResMut<PendingRebind>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    let Some(action) = pending_rebind.action else {
+        return;
+    };
+
+    // Escape cancels, so once moved off Pause only the defaults bring it back.
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        pending_rebind.action = None;
+        return;
+    }
+
+    let pushed_axis = || {
+        active_gamepad.0.and_then(|gamepad| {
+            CAPTURE_AXES.into_iter().find_map(|axis_type| {
+                let value = gamepad_axes.get(GamepadAxis::new(gamepad, axis_type))?;
+                let direction = if value >= CAPTURE_AXIS_THRESHOLD {
+                    AxisDirection::Positive
+                } else if value <= -CAPTURE_AXIS_THRESHOLD {
+                    AxisDirection::Negative
+                } else {
+                    return None;
+                };
+                Some(Binding::GamepadAxis(axis_type, direction))
+            })
+        })
+    };
+
+    let binding = keyboard_input
+        .get_just_pressed()
+        .next()
+        .map(|key_code| Binding::Key(*key_code))
+        .or_else(|| {
+            mouse_input
+                .get_just_pressed()
+                .next()
+                .map(|button| Binding::Mouse(*button))
+        })
+        .or_else(|| {
+            gamepad_buttons
+                .get_just_pressed()
+                .find(|button| Some(button.gamepad) == active_gamepad.0)
+                .map(|button| Binding::GamepadButton(button.button_type))
+        })
+        .or_else(pushed_axis);
+
+    if let Some(binding) = binding {
+        bindings.rebind(action, pending_rebind.slot, binding);
+        pending_rebind.action = None;
+        save_bindings(&bindings);
+    }
+}
+
+fn save_bindings(bindings: &InputBindings) {
+    let Some(path) = user_bindings_path() else {
+        warn!("Could not find a config directory to save the bindings in");
+        return;
+    };
+
+    if let Err(error) = bindings.save(&path) {
+        error!("Could not save bindings to {}: {error}", path.display());
+    }
+}
+
+pub fn handle_controls_menu_buttons(
+    mut pressed_events: EventReader<MenuButtonPressed>,
+    mut pending_rebind: ResMut<PendingRebind>,
+    mut bindings: ResMut<InputBindings>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    for pressed in pressed_events.iter() {
+        match pressed.action {
+            MenuAction::Rebind(action, slot) => {
+                pending_rebind.action = Some(action);
+                pending_rebind.slot = slot;
+            }
+            MenuAction::ResetBindings => {
+                *bindings = InputBindings::default();
+                pending_rebind.action = None;
+                save_bindings(&bindings);
+            }
+            MenuAction::Back => app_state_next_state.set(AppState::MainMenu),
+            _ => {}
+        }
+    }
+}
+
+/// Lists the bindings in each slot, in red when another action shares one of them.
+pub fn update_binding_labels(
+    bindings: Res<InputBindings>,
+    pending_rebind: Res<PendingRebind>,
+    button_query: Query<(&Children, &MenuButton)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let conflicts = bindings.conflicts();
+
+    for (children, menu_button) in button_query.iter() {
+        let MenuAction::Rebind(action, slot) = menu_button.action else {
+            continue;
+        };
+        let slot_bindings = bindings.slot(action, slot);
+
+        let label = if pending_rebind.action == Some(action) && pending_rebind.slot == slot {
+            "...".to_string()
+        } else if slot_bindings.is_empty() {
+            "Unbound".to_string()
+        } else {
+            slot_bindings
+                .iter()
+                .map(|binding| binding.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let color = if conflicts
+            .iter()
+            .any(|(binding, _)| slot_bindings.contains(binding))
+        {
+            CONFLICT_TEXT_COLOR
+        } else {
+            Color::WHITE
+        };
+
+        set_button_label(children, &mut text_query, &label);
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                if text.sections[0].style.color != color {
+                    text.sections[0].style.color = color;
+                }
+            }
+        }
+    }
+}
+
+pub fn update_controls_status(
+    bindings: Res<InputBindings>,
+    pending_rebind: Res<PendingRebind>,
+    mut status_query: Query<&mut Text, With<ControlsStatus>>,
+) {
+    let conflicts = bindings.conflicts();
+
+    let status = if let Some(action) = pending_rebind.action {
+        format!(
+            "Press a key, mouse button or gamepad input for {}, Esc cancels",
+            action.name()
+        )
+    } else if conflicts.is_empty() {
+        "Click an action to change its binding".to_string()
+    } else {
+        conflicts
+            .iter()
+            .map(|(binding, actions)| {
+                let names: Vec<&str> = actions.iter().map(|action| action.name()).collect();
+                format!("{binding} is bound to {}", names.join(" and "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    for mut text in status_query.iter_mut() {
+        if text.sections[0].value != status {
+            text.sections[0].value = status.clone();
+        }
+    }
+}
+
+/// Keeps the arrow keys, Tab and Enter free to be bound while a rebind is pending.
+pub fn suspend_focus_while_rebinding(
+    pending_rebind: Res<PendingRebind>,
+    mut focus: ResMut<UiFocus>,
+) {
+    let suspended = pending_rebind.action.is_some();
+    if focus.suspended != suspended {
+        focus.suspended = suspended;
+    }
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use input_map::{actions::Action, bindings::BINDING_SLOTS};
+
+use crate::controls_menu::components::*;
+use crate::controls_menu::styles::*;
+use crate::menu::components::MenuAction;
+use crate::menu::screen::{MenuItem, MenuScreen};
+use crate::menu::styles::get_text_style;
+use crate::ui_focus::components::UiFocus;
+
+pub fn spawn_controls_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    build_controls_menu(&mut commands, &asset_server);
+}
+
+pub fn despawn_controls_menu(
+    mut commands: Commands,
+    controls_menu_query: Query<Entity, With<ControlsMenu>>,
+    mut pending_rebind: ResMut<PendingRebind>,
+    mut focus: ResMut<UiFocus>,
+) {
+    if let Ok(controls_menu_entity) = controls_menu_query.get_single() {
+        commands.entity(controls_menu_entity).despawn_recursive();
+    }
+    pending_rebind.action = None;
+    focus.suspended = false;
+}
+
+pub fn build_controls_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    // === Binding Rows ===
+    let mut items: Vec<MenuItem> = Action::ALL
+        .into_iter()
+        .map(|action| {
+            let label = MenuItem::Label {
+                text: action.name().to_string(),
+                style: ACTION_LABEL_STYLE,
+                font_size: BINDING_FONT_SIZE,
+            };
+            // The labels list the current bindings of each slot.
+            let slots = (0..BINDING_SLOTS).map(|slot| MenuItem::Button {
+                label: String::new(),
+                action: MenuAction::Rebind(action, slot),
+                style: BINDING_BUTTON_STYLE,
+                font_size: BINDING_FONT_SIZE,
+            });
+
+            MenuItem::Row {
+                style: BINDING_ROW_STYLE,
+                items: std::iter::once(label).chain(slots).collect(),
+            }
+        })
+        .collect();
+    // === Buttons ===
+    items.push(MenuItem::row(vec![
+        MenuItem::button("Defaults", MenuAction::ResetBindings),
+        MenuItem::button("Back", MenuAction::Back),
+    ]));
+
+    let controls_menu_entity = MenuScreen {
+        title: "Controls",
+        style: CONTROLS_MENU_STYLE,
+        items,
+        ..default()
+    }
+    .spawn(commands, asset_server, ControlsMenu {});
+
+    // === Status ===
+    // Between the binding rows and the buttons, after the title.
+    let status_entity = commands
+        .spawn((
+            TextBundle::from_section("", get_text_style(asset_server, BINDING_FONT_SIZE))
+                .with_style(STATUS_STYLE),
+            ControlsStatus {},
+        ))
+        .id();
+    commands
+        .entity(controls_menu_entity)
+        .insert_children(Action::ALL.len() + 1, &[status_entity]);
+
+    controls_menu_entity
+}
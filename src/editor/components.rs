@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+use super::document::Selection;
+use crate::tank::components::Team;
+
+/// What clicking into the level does.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EditorTool {
+    #[default]
+    Select,
+    Wall,
+    Water,
+    Prop,
+    Spawn(Team),
+}
+
+impl EditorTool {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Select => "Select",
+            Self::Wall => "Wall",
+            Self::Water => "Water",
+            Self::Prop => "Prop",
+            Self::Spawn(Team::Red) => "Red spawn",
+            Self::Spawn(Team::Blue) => "Blue spawn",
+        }
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct EditorSettings {
+    pub grid_size: f32,
+    pub snap: bool,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            grid_size: 20.0,
+            snap: true,
+        }
+    }
+}
+
+/// The outline being drawn with the wall, water or prop tool.
+#[derive(Resource, Default)]
+pub struct EditorDraft {
+    pub points: Vec<Vec2>,
+}
+
+#[derive(Resource, Default)]
+pub struct EditorSelection {
+    pub selected: Option<Selection>,
+    /// Where the cursor was when the selection was last moved, while dragging.
+    pub drag_from: Option<Vec2>,
+    /// Whether the current drag has moved anything yet and made an undo checkpoint.
+    pub moved: bool,
+}
+
+/// Where the cursor points in the level, if it is over the window.
+#[derive(Resource, Default)]
+pub struct EditorCursor {
+    pub position: Option<Vec2>,
+    /// `position` rounded to the grid when snapping is on.
+    pub snapped: Option<Vec2>,
+}
+
+/// Despawned when the editor closes.
+#[derive(Component)]
+pub struct EditorEntity;
+
+#[derive(Component)]
+pub struct EditorStatus;
@@ -0,0 +1,284 @@
+use bevy::prelude::*;
+
+use crate::level::definition::*;
+
+/// How many edits can be undone.
+const MAX_HISTORY: usize = 100;
+
+/// Something in the level that can be selected, moved and deleted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selection {
+    Wall(usize),
+    Water(usize),
+    Prop(usize),
+    Spawn(usize),
+}
+
+/// The level being edited, with undo and redo.
+///
+/// Every edit calls `checkpoint` first, which remembers the level as it was. A drag is
+/// a single edit no matter how many frames it lasts.
+#[derive(Resource)]
+pub struct EditorDocument {
+    pub level: LevelAsset,
+    /// Where the level is saved, relative to `assets`.
+    pub path: String,
+    undo: Vec<LevelAsset>,
+    redo: Vec<LevelAsset>,
+}
+
+impl Default for EditorDocument {
+    fn default() -> Self {
+        Self::new(
+            LevelAsset {
+                name: "Untitled".to_string(),
+                ..default()
+            },
+            "levels/untitled.level.ron".to_string(),
+        )
+    }
+}
+
+impl EditorDocument {
+    pub fn new(level: LevelAsset, path: String) -> Self {
+        Self {
+            level,
+            path,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    pub fn checkpoint(&mut self) {
+        self.undo.push(self.level.clone());
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some(level) => {
+                self.redo.push(std::mem::replace(&mut self.level, level));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(level) => {
+                self.undo.push(std::mem::replace(&mut self.level, level));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The topmost thing under `point`. Spawn points and open walls are picked
+    /// within `tolerance` of their position or line.
+    pub fn pick(&self, point: Vec2, tolerance: f32) -> Option<Selection> {
+        let level = &self.level;
+
+        let spawn = level
+            .spawns
+            .iter()
+            .rposition(|spawn| spawn.position.distance(point) <= tolerance)
+            .map(Selection::Spawn);
+        let prop = level
+            .props
+            .iter()
+            .rposition(|prop| contains(&prop.points, point))
+            .map(Selection::Prop);
+        let wall = level
+            .walls
+            .iter()
+            .rposition(|wall| {
+                if wall.open {
+                    distance_to_polyline(&wall.points, point) <= tolerance
+                } else {
+                    contains(&wall.points, point)
+                }
+            })
+            .map(Selection::Wall);
+        let water = level
+            .water
+            .iter()
+            .rposition(|water| contains(&water.points, point))
+            .map(Selection::Water);
+
+        spawn.or(wall).or(prop).or(water)
+    }
+
+    pub fn translate(&mut self, selection: Selection, offset: Vec2) {
+        let level = &mut self.level;
+        let points = match selection {
+            Selection::Wall(index) => level.walls.get_mut(index).map(|wall| &mut wall.points),
+            Selection::Water(index) => level.water.get_mut(index).map(|water| &mut water.points),
+            Selection::Prop(index) => level.props.get_mut(index).map(|prop| &mut prop.points),
+            Selection::Spawn(index) => {
+                if let Some(spawn) = level.spawns.get_mut(index) {
+                    spawn.position += offset;
+                }
+                return;
+            }
+        };
+
+        for point in points.into_iter().flatten() {
+            *point += offset;
+        }
+    }
+
+    pub fn remove(&mut self, selection: Selection) {
+        let level = &mut self.level;
+        match selection {
+            Selection::Wall(index) if index < level.walls.len() => {
+                level.walls.remove(index);
+            }
+            Selection::Water(index) if index < level.water.len() => {
+                level.water.remove(index);
+            }
+            Selection::Prop(index) if index < level.props.len() => {
+                level.props.remove(index);
+            }
+            Selection::Spawn(index) if index < level.spawns.len() => {
+                level.spawns.remove(index);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Rounds `point` to the nearest corner of a grid with cells of `grid_size`.
+pub fn snap(point: Vec2, grid_size: f32) -> Vec2 {
+    (point / grid_size).round() * grid_size
+}
+
+/// Even-odd test, so it works for concave polygons too.
+fn contains(polygon: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+
+    for (index, a) in polygon.iter().enumerate() {
+        let b = polygon[(index + 1) % polygon.len()];
+        if (a.y > point.y) != (b.y > point.y) {
+            let crossing = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if point.x < crossing {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+fn distance_to_polyline(points: &[Vec2], point: Vec2) -> f32 {
+    points
+        .windows(2)
+        .map(|segment| {
+            let (a, b) = (segment[0], segment[1]);
+            let t = ((point - a).dot(b - a) / (b - a).length_squared().max(f32::EPSILON))
+                .clamp(0.0, 1.0);
+            point.distance(a + (b - a) * t)
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tank::components::Team;
+
+    fn square(min: Vec2, size: f32) -> Vec<Vec2> {
+        vec![
+            min,
+            min + Vec2::new(size, 0.0),
+            min + Vec2::splat(size),
+            min + Vec2::new(0.0, size),
+        ]
+    }
+
+    fn document() -> EditorDocument {
+        let mut document = EditorDocument::default();
+        document.level.walls.push(WallDefinition {
+            points: square(Vec2::ZERO, 100.0),
+            color: default_wall_color(),
+            open: false,
+            destructible: None,
+        });
+        document.level.spawns.push(SpawnPoint {
+            team: Team::Red,
+            position: Vec2::new(50.0, 50.0),
+            rotation: 0.0,
+        });
+        document
+    }
+
+    #[test]
+    fn undo_and_redo_restore_edits() {
+        let mut document = document();
+
+        document.checkpoint();
+        document.translate(Selection::Wall(0), Vec2::new(10.0, 0.0));
+        document.checkpoint();
+        document.remove(Selection::Spawn(0));
+
+        assert!(document.undo());
+        assert_eq!(document.level.spawns.len(), 1);
+        assert!(document.undo());
+        assert_eq!(document.level.walls[0].points[0], Vec2::ZERO);
+        assert!(!document.undo());
+
+        assert!(document.redo());
+        assert_eq!(document.level.walls[0].points[0], Vec2::new(10.0, 0.0));
+    }
+
+    #[test]
+    fn new_edits_clear_redo() {
+        let mut document = document();
+
+        document.checkpoint();
+        document.remove(Selection::Spawn(0));
+        document.undo();
+        document.checkpoint();
+        document.remove(Selection::Wall(0));
+
+        assert!(!document.redo());
+    }
+
+    #[test]
+    fn spawns_are_picked_over_walls() {
+        let document = document();
+
+        assert_eq!(
+            document.pick(Vec2::new(52.0, 50.0), 10.0),
+            Some(Selection::Spawn(0))
+        );
+        assert_eq!(
+            document.pick(Vec2::new(10.0, 90.0), 10.0),
+            Some(Selection::Wall(0))
+        );
+        assert_eq!(document.pick(Vec2::new(150.0, 50.0), 10.0), None);
+    }
+
+    #[test]
+    fn concave_polygons_are_picked_inside_only() {
+        let l_shape = [
+            Vec2::ZERO,
+            Vec2::new(100.0, 0.0),
+            Vec2::new(100.0, 20.0),
+            Vec2::new(20.0, 20.0),
+            Vec2::new(20.0, 100.0),
+            Vec2::new(0.0, 100.0),
+        ];
+
+        assert!(contains(&l_shape, Vec2::new(10.0, 50.0)));
+        assert!(!contains(&l_shape, Vec2::new(50.0, 50.0)));
+    }
+
+    #[test]
+    fn snap_rounds_to_nearest_corner() {
+        assert_eq!(snap(Vec2::new(24.0, -26.0), 50.0), Vec2::new(0.0, -50.0));
+    }
+}
@@ -0,0 +1,48 @@
+pub mod components;
+pub mod document;
+mod systems;
+
+use bevy::prelude::*;
+
+use components::*;
+use document::EditorDocument;
+use systems::*;
+
+use crate::AppState;
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EditorDocument>()
+            .init_resource::<EditorTool>()
+            .init_resource::<EditorSettings>()
+            .init_resource::<EditorDraft>()
+            .init_resource::<EditorSelection>()
+            .init_resource::<EditorCursor>()
+            .add_system(open_editor.in_schedule(OnEnter(AppState::Editor)))
+            .add_systems(
+                (
+                    select_editor_tool,
+                    track_editor_cursor,
+                    drag_selection,
+                    draw_outlines,
+                    place_spawns,
+                    edit_selection,
+                    undo_redo,
+                )
+                    .chain()
+                    .in_set(OnUpdate(AppState::Editor)),
+            )
+            .add_systems(
+                (
+                    save_level,
+                    draw_editor_grid,
+                    draw_editor,
+                    update_editor_status,
+                )
+                    .in_set(OnUpdate(AppState::Editor)),
+            )
+            .add_system(close_editor.in_schedule(OnExit(AppState::Editor)));
+    }
+}
@@ -0,0 +1,537 @@
+use std::f32::consts::TAU;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_prototype_debug_lines::DebugLines;
+use pancam::PanCam;
+
+use super::components::*;
+use super::document::*;
+use crate::hud::styles::{get_hud_text_style, HUD_STYLE};
+use crate::level::{
+    components::{LevelLibrary, SelectedLevel},
+    definition::*,
+};
+use crate::systems::get_cursor_position;
+use crate::tank::components::Team;
+use crate::MainCamera;
+
+/// How close to a spawn point or fence a click has to be to select it.
+const PICK_TOLERANCE: f32 = 12.0;
+const SPAWN_MARKER_RADIUS: f32 = 15.0;
+/// Degrees the selected spawn point turns per key press.
+const SPAWN_ROTATION_STEP: f32 = 45.0;
+/// The grid gets coarser when zoomed out so no more lines than this are drawn per axis.
+const MAX_GRID_LINES: f32 = 100.0;
+
+const GRID_COLOR: Color = Color::rgba(1.0, 1.0, 1.0, 0.08);
+const WATER_COLOR: Color = Color::rgb(0.18, 0.37, 0.56);
+const SELECTION_COLOR: Color = Color::YELLOW;
+const DRAFT_COLOR: Color = Color::GREEN;
+const PROP_COLOR: &str = "3a3a3a";
+
+const HELP: &str = "1 Select  2 Wall  3 Water  4 Prop  5 Red spawn  6 Blue spawn\n\
+    Right click closes an outline, Enter leaves a wall open as a fence, Esc cancels\n\
+    Drag to move, Del deletes, R rotates spawns, Q toggles snapping\n\
+    Ctrl+Z undo, Ctrl+Y redo, Ctrl+S save, G play, M menu";
+
+/// Opens the level selected in the main menu, or an empty one if it does not exist.
+pub fn open_editor(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    selected_level: Res<SelectedLevel>,
+    library: Res<LevelLibrary>,
+    levels: Res<Assets<LevelAsset>>,
+    mut pan_cam_query: Query<&mut PanCam, With<MainCamera>>,
+) {
+    let document = match library.get(&levels, &selected_level.0) {
+        Some(level) => {
+            // Levels drawn in SVG or generated are saved as new RON files.
+            let path = library
+                .levels
+                .iter()
+                .filter(|handle| {
+                    levels
+                        .get(handle)
+                        .is_some_and(|level| level.name == selected_level.0)
+                })
+                .find_map(|handle| asset_server.get_handle_path(handle.id()))
+                .map(|path| path.path().to_string_lossy().replace('\\', "/"))
+                .filter(|path| path.ends_with(".level.ron"))
+                .unwrap_or_else(|| level_file(&level.name));
+
+            EditorDocument::new(level.clone(), path)
+        }
+        None => EditorDocument::default(),
+    };
+
+    commands.insert_resource(document);
+    commands.insert_resource(EditorDraft::default());
+    commands.insert_resource(EditorSelection::default());
+
+    // Left and right clicks edit the level, the middle button still pans.
+    for mut pan_cam in pan_cam_query.iter_mut() {
+        pan_cam.grab_buttons = vec![MouseButton::Middle];
+    }
+
+    commands.spawn((
+        TextBundle::from_section("", get_hud_text_style(&asset_server)).with_style(HUD_STYLE),
+        EditorStatus,
+        EditorEntity,
+        Name::new("Editor Status"),
+    ));
+}
+
+pub fn close_editor(
+    mut commands: Commands,
+    editor_query: Query<Entity, With<EditorEntity>>,
+    mut pan_cam_query: Query<&mut PanCam, With<MainCamera>>,
+) {
+    for entity in editor_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    for mut pan_cam in pan_cam_query.iter_mut() {
+        pan_cam.grab_buttons = PanCam::default().grab_buttons;
+    }
+}
+
+fn level_file(name: &str) -> String {
+    format!("levels/{}.level.ron", name.to_lowercase().replace(' ', "_"))
+}
+
+pub fn select_editor_tool(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut tool: ResMut<EditorTool>,
+    mut settings: ResMut<EditorSettings>,
+    mut draft: ResMut<EditorDraft>,
+    mut selection: ResMut<EditorSelection>,
+) {
+    let tools = [
+        (KeyCode::Key1, EditorTool::Select),
+        (KeyCode::Key2, EditorTool::Wall),
+        (KeyCode::Key3, EditorTool::Water),
+        (KeyCode::Key4, EditorTool::Prop),
+        (KeyCode::Key5, EditorTool::Spawn(Team::Red)),
+        (KeyCode::Key6, EditorTool::Spawn(Team::Blue)),
+    ];
+
+    for (key_code, key_tool) in tools {
+        if keyboard_input.just_pressed(key_code) && *tool != key_tool {
+            *tool = key_tool;
+            draft.points.clear();
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Q) {
+        settings.snap = !settings.snap;
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        draft.points.clear();
+        *selection = EditorSelection::default();
+    }
+}
+
+/// Keeps `EditorCursor` up to date for the tools.
+pub fn track_editor_cursor(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    settings: Res<EditorSettings>,
+    mut cursor: ResMut<EditorCursor>,
+) {
+    let position = match (window_query.get_single(), camera_query.get_single()) {
+        (Ok(window), Ok((camera, camera_transform))) => {
+            get_cursor_position(window, camera, camera_transform)
+        }
+        _ => None,
+    };
+
+    *cursor = EditorCursor {
+        position,
+        snapped: position.map(|position| snapped(&settings, position)),
+    };
+}
+
+pub fn drag_selection(
+    mouse_input: Res<Input<MouseButton>>,
+    cursor: Res<EditorCursor>,
+    tool: Res<EditorTool>,
+    mut document: ResMut<EditorDocument>,
+    mut selection: ResMut<EditorSelection>,
+) {
+    let (EditorTool::Select, Some(position), Some(point)) =
+        (*tool, cursor.position, cursor.snapped)
+    else {
+        return;
+    };
+
+    if mouse_input.just_pressed(MouseButton::Left) {
+        selection.selected = document.pick(position, PICK_TOLERANCE);
+        selection.drag_from = selection.selected.map(|_| point);
+        selection.moved = false;
+    }
+
+    if let (Some(selected), Some(drag_from)) = (selection.selected, selection.drag_from) {
+        let offset = point - drag_from;
+
+        if mouse_input.pressed(MouseButton::Left) && offset != Vec2::ZERO {
+            if !selection.moved {
+                document.checkpoint();
+                selection.moved = true;
+            }
+            document.translate(selected, offset);
+            selection.drag_from = Some(point);
+        }
+    }
+
+    if mouse_input.just_released(MouseButton::Left) {
+        selection.drag_from = None;
+    }
+}
+
+/// Left clicks add corners to the draft, a right click closes it into a wall, water or
+/// prop. Enter turns a wall draft into an open fence.
+pub fn draw_outlines(
+    mouse_input: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    cursor: Res<EditorCursor>,
+    tool: Res<EditorTool>,
+    mut document: ResMut<EditorDocument>,
+    mut draft: ResMut<EditorDraft>,
+) {
+    if !matches!(
+        *tool,
+        EditorTool::Wall | EditorTool::Water | EditorTool::Prop
+    ) {
+        return;
+    }
+    let Some(point) = cursor.snapped else {
+        return;
+    };
+
+    if mouse_input.just_pressed(MouseButton::Left) && draft.points.last() != Some(&point) {
+        draft.points.push(point);
+    }
+
+    let close = mouse_input.just_pressed(MouseButton::Right) && draft.points.len() >= 3;
+    let fence = keyboard_input.just_pressed(KeyCode::Return)
+        && *tool == EditorTool::Wall
+        && draft.points.len() >= 2;
+
+    if !close && !fence {
+        return;
+    }
+
+    let points = std::mem::take(&mut draft.points);
+    document.checkpoint();
+
+    let level = &mut document.level;
+    match *tool {
+        EditorTool::Wall => level.walls.push(WallDefinition {
+            points,
+            color: default_wall_color(),
+            open: fence,
+            destructible: None,
+        }),
+        EditorTool::Water => level.water.push(WaterDefinition { points }),
+        _ => level.props.push(PropDefinition {
+            points,
+            color: PROP_COLOR.to_string(),
+            z: default_prop_z(),
+            destructible: None,
+        }),
+    }
+}
+
+pub fn place_spawns(
+    mouse_input: Res<Input<MouseButton>>,
+    cursor: Res<EditorCursor>,
+    tool: Res<EditorTool>,
+    mut document: ResMut<EditorDocument>,
+) {
+    let (EditorTool::Spawn(team), Some(point)) = (*tool, cursor.snapped) else {
+        return;
+    };
+
+    if mouse_input.just_pressed(MouseButton::Left) {
+        document.checkpoint();
+        document.level.spawns.push(SpawnPoint {
+            team,
+            position: point,
+            rotation: if team == Team::Red { 0.0 } else { 180.0 },
+        });
+    }
+}
+
+fn snapped(settings: &EditorSettings, point: Vec2) -> Vec2 {
+    if settings.snap {
+        snap(point, settings.grid_size)
+    } else {
+        point
+    }
+}
+
+pub fn edit_selection(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut document: ResMut<EditorDocument>,
+    mut selection: ResMut<EditorSelection>,
+) {
+    let Some(selected) = selection.selected else {
+        return;
+    };
+
+    if keyboard_input.just_pressed(KeyCode::Delete) {
+        document.checkpoint();
+        document.remove(selected);
+        *selection = EditorSelection::default();
+    }
+
+    if keyboard_input.just_pressed(KeyCode::R) {
+        if let Selection::Spawn(index) = selected {
+            document.checkpoint();
+            if let Some(spawn) = document.level.spawns.get_mut(index) {
+                spawn.rotation = (spawn.rotation + SPAWN_ROTATION_STEP) % 360.0;
+            }
+        }
+    }
+}
+
+pub fn undo_redo(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut document: ResMut<EditorDocument>,
+    mut selection: ResMut<EditorSelection>,
+) {
+    if !keyboard_input.any_pressed([KeyCode::LControl, KeyCode::RControl]) {
+        return;
+    }
+    let shift = keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+
+    let changed = if keyboard_input.just_pressed(KeyCode::Z) && !shift {
+        document.undo()
+    } else if keyboard_input.just_pressed(KeyCode::Y)
+        || (keyboard_input.just_pressed(KeyCode::Z) && shift)
+    {
+        document.redo()
+    } else {
+        false
+    };
+
+    // Indices may point at something else after the level changed under them.
+    if changed {
+        *selection = EditorSelection::default();
+    }
+}
+
+/// Writes the level to its file and makes it playable right away.
+pub fn save_level(
+    keyboard_input: Res<Input<KeyCode>>,
+    document: Res<EditorDocument>,
+    mut library: ResMut<LevelLibrary>,
+    mut levels: ResMut<Assets<LevelAsset>>,
+) {
+    if !keyboard_input.any_pressed([KeyCode::LControl, KeyCode::RControl])
+        || !keyboard_input.just_pressed(KeyCode::S)
+    {
+        return;
+    }
+
+    let serialized = match ron::ser::to_string_pretty(&document.level, Default::default()) {
+        Ok(serialized) => serialized,
+        Err(error) => {
+            error!("Could not serialize level {}: {error}", document.level.name);
+            return;
+        }
+    };
+
+    let path = std::path::Path::new("assets").join(&document.path);
+    if let Err(error) = std::fs::write(&path, serialized) {
+        error!("Could not save level to {}: {error}", path.display());
+        return;
+    }
+    info!("Saved level {} to {}", document.level.name, path.display());
+
+    let existing = library
+        .levels
+        .iter()
+        .find(|handle| {
+            levels
+                .get(handle)
+                .is_some_and(|level| level.name == document.level.name)
+        })
+        .cloned();
+
+    match existing.and_then(|handle| levels.get_mut(&handle)) {
+        Some(level) => *level = document.level.clone(),
+        None => {
+            let handle = levels.add(document.level.clone());
+            library.levels.push(handle);
+        }
+    }
+}
+
+pub fn draw_editor_grid(
+    mut lines: ResMut<DebugLines>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    settings: Res<EditorSettings>,
+) {
+    if let (Ok(window), Ok((camera, camera_transform))) =
+        (window_query.get_single(), camera_query.get_single())
+    {
+        draw_grid(
+            &mut lines,
+            window,
+            camera,
+            camera_transform,
+            settings.grid_size,
+        );
+    }
+}
+
+pub fn draw_editor(
+    mut lines: ResMut<DebugLines>,
+    cursor: Res<EditorCursor>,
+    document: Res<EditorDocument>,
+    draft: Res<EditorDraft>,
+    selection: Res<EditorSelection>,
+) {
+    let level = &document.level;
+    let color_of = |selected: Selection, color: Color| {
+        if selection.selected == Some(selected) {
+            SELECTION_COLOR
+        } else {
+            color
+        }
+    };
+
+    for (index, water) in level.water.iter().enumerate() {
+        let color = color_of(Selection::Water(index), WATER_COLOR);
+        draw_outline(&mut lines, &water.points, true, color);
+    }
+    for (index, prop) in level.props.iter().enumerate() {
+        let color = color_of(Selection::Prop(index), prop.color());
+        draw_outline(&mut lines, &prop.points, true, color);
+    }
+    for (index, wall) in level.walls.iter().enumerate() {
+        let color = color_of(Selection::Wall(index), wall.color());
+        draw_outline(&mut lines, &wall.points, !wall.open, color);
+    }
+    for (index, spawn) in level.spawns.iter().enumerate() {
+        let team_color = match spawn.team {
+            Team::Red => Color::RED,
+            Team::Blue => Color::BLUE,
+        };
+        let color = color_of(Selection::Spawn(index), team_color);
+        draw_spawn(&mut lines, spawn, color);
+    }
+    for pickup in level.pickups.iter() {
+        let circle = circle_points(pickup.position, SPAWN_MARKER_RADIUS / 2.0);
+        draw_outline(&mut lines, &circle, true, Color::GREEN);
+    }
+
+    // The draft follows the cursor to show where the next corner goes.
+    let mut points = draft.points.clone();
+    if let (false, Some(point)) = (points.is_empty(), cursor.snapped) {
+        points.push(point);
+    }
+    draw_outline(&mut lines, &points, false, DRAFT_COLOR);
+}
+
+fn draw_grid(
+    lines: &mut DebugLines,
+    window: &Window,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    grid_size: f32,
+) {
+    let corners = [Vec2::ZERO, Vec2::new(window.width(), window.height())]
+        .map(|corner| camera.viewport_to_world(camera_transform, corner));
+    let [Some(a), Some(b)] = corners else {
+        return;
+    };
+    let min = a.origin.truncate().min(b.origin.truncate());
+    let max = a.origin.truncate().max(b.origin.truncate());
+
+    let mut step = grid_size;
+    while (max - min).max_element() / step > MAX_GRID_LINES {
+        step *= 5.0;
+    }
+
+    let start = (min / step).floor() * step;
+    let mut x = start.x;
+    while x <= max.x {
+        lines.line_colored(
+            Vec3::new(x, min.y, 0.),
+            Vec3::new(x, max.y, 0.),
+            0.0,
+            GRID_COLOR,
+        );
+        x += step;
+    }
+    let mut y = start.y;
+    while y <= max.y {
+        lines.line_colored(
+            Vec3::new(min.x, y, 0.),
+            Vec3::new(max.x, y, 0.),
+            0.0,
+            GRID_COLOR,
+        );
+        y += step;
+    }
+}
+
+fn draw_outline(lines: &mut DebugLines, points: &[Vec2], closed: bool, color: Color) {
+    for segment in points.windows(2) {
+        lines.line_colored(segment[0].extend(0.), segment[1].extend(0.), 0.0, color);
+    }
+
+    if let (true, Some(first), Some(last)) =
+        (closed && points.len() > 2, points.first(), points.last())
+    {
+        lines.line_colored(last.extend(0.), first.extend(0.), 0.0, color);
+    }
+}
+
+fn draw_spawn(lines: &mut DebugLines, spawn: &SpawnPoint, color: Color) {
+    let circle = circle_points(spawn.position, SPAWN_MARKER_RADIUS);
+    draw_outline(lines, &circle, true, color);
+
+    let facing = Vec2::from_angle(spawn.rotation.to_radians()) * SPAWN_MARKER_RADIUS * 2.0;
+    lines.line_colored(
+        spawn.position.extend(0.),
+        (spawn.position + facing).extend(0.),
+        0.0,
+        color,
+    );
+}
+
+fn circle_points(center: Vec2, radius: f32) -> Vec<Vec2> {
+    (0..16)
+        .map(|index| center + Vec2::from_angle(index as f32 / 16.0 * TAU) * radius)
+        .collect()
+}
+
+pub fn update_editor_status(
+    tool: Res<EditorTool>,
+    settings: Res<EditorSettings>,
+    document: Res<EditorDocument>,
+    mut status_query: Query<&mut Text, With<EditorStatus>>,
+) {
+    let snap = if settings.snap {
+        format!("{} px", settings.grid_size)
+    } else {
+        "off".to_string()
+    };
+    let status = format!(
+        "{} ({})\nTool: {}   Snap: {snap}\n\n{HELP}",
+        document.level.name,
+        document.path,
+        tool.name(),
+    );
+
+    for mut text in status_query.iter_mut() {
+        if text.sections[0].value != status {
+            text.sections[0].value = status.clone();
+        }
+    }
+}
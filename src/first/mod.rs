@@ -2,16 +2,18 @@ pub mod components;
 pub mod objects;
 mod systems;
 
-use bevy::prelude::{App, Plugin};
+use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::ShapePlugin;
 use systems::*;
 
+use crate::AppState;
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(ShapePlugin)
-            .add_startup_system(setup_system)
-            .add_startup_system(spawn_enemies);
+            .add_system(spawn_enemies.in_schedule(OnEnter(AppState::Game)))
+            .add_system(spawn_tanks_on_spawn_points.in_set(OnUpdate(AppState::Game)));
     }
 }
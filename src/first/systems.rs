@@ -1,170 +1,93 @@
 use bevy::{prelude::*, window::PrimaryWindow};
-use bevy_prototype_lyon::{path, prelude::*};
+use bevy_prototype_lyon::prelude::*;
 use bevy_rapier2d::prelude::*;
 use rand::prelude::*;
 
 use super::components::*;
+use crate::ai::bundles::AiTankBundle;
+use crate::combat::components::Health;
+use crate::level::{components::LevelSeed, definition::SpawnPoint, events::LevelSpawned};
+use crate::navigation::components::{FlowFieldAgent, FlowFieldGoal};
 use crate::player::components::*;
-
-#[derive(Bundle)]
-struct TankBodyBundle {
-    #[bundle]
-    shape: ShapeBundle,
-    fill: Fill,
-    stroke: Stroke,
-}
-
-impl TankBodyBundle {
-    fn new() -> Self {
-        let body = shapes::Polygon {
-            points: vec![
-                Vec2::new(-20.0, -15.0),
-                Vec2::new(-20.0, 15.0),
-                Vec2::new(20.0, 15.0),
-                Vec2::new(20.0, -15.0),
-            ],
-            closed: true,
-        };
-
-        let mut path_builder = path::PathBuilder::new();
-        path_builder.move_to(Vec2::new(-20.0, -15.0));
-        path_builder.line_to(Vec2::new(20.0, 0.0));
-        path_builder.line_to(Vec2::new(-20.0, 15.0));
-        let path = path_builder.build();
-
-        let mut stroke = Stroke::new(Color::hex("191919").unwrap(), 2.0);
-        stroke.options.line_join = LineJoin::Round;
-
-        let mut fill = Fill::color(Color::hex("bf3030").unwrap());
-        fill.options.fill_rule = FillRule::NonZero;
-
-        Self {
-            shape: ShapeBundle {
-                path: GeometryBuilder::new().add(&body).add(&path).build(),
-                transform: Transform {
-                    translation: Vec3::new(0.0, 0.0, 0.0),
-                    ..default()
-                },
-                ..default()
-            },
-            fill,
-            stroke,
+use crate::tank::components::*;
+use crate::GameSessionEntity;
+
+/// Puts the player on the first red spawn point of a freshly spawned level and
+/// AI tanks on all the others.
+pub fn spawn_tanks_on_spawn_points(
+    mut commands: Commands,
+    mut spawned_events: EventReader<LevelSpawned>,
+    asset_server: Res<AssetServer>,
+    loadout: Res<Loadout>,
+) {
+    for event in spawned_events.iter() {
+        let player_spawn = event
+            .spawns
+            .iter()
+            .position(|spawn| spawn.team == Team::Red);
+
+        if player_spawn.is_none() {
+            warn!("The level has no red spawn point for the player");
         }
-    }
-}
 
-impl Default for TankBodyBundle {
-    fn default() -> Self {
-        Self::new()
+        for (index, spawn) in event.spawns.iter().enumerate() {
+            if Some(index) == player_spawn {
+                spawn_player(&mut commands, &asset_server, &loadout, spawn.transform());
+            } else {
+                spawn_ai_tank(&mut commands, &asset_server, spawn);
+            }
+        }
     }
 }
 
-pub fn setup_system(mut commands: Commands) {
-    let turret = shapes::Circle {
-        radius: 10.0,
-        ..default()
-    };
-
-    let gun = shapes::Polygon {
-        points: vec![
-            Vec2::new(0.0, -5.0),
-            Vec2::new(0.0, 5.0),
-            Vec2::new(30.0, 5.0),
-            Vec2::new(30.0, -5.0),
-        ],
-        closed: true,
-    };
-
-    let body = commands
-        .spawn((
-            Name::new("Player Body"),
-            TankBodyBundle::new(),
-            ExampleShape,
-            Player {
-                speed: 250.,
-                pull_distance: 10.,
-                ..default()
-            },
-            RigidBody::Dynamic,
-            Damping {
-                linear_damping: 50.0,
-                angular_damping: 25.,
-            },
-            Velocity::default(),
-            Collider::cuboid(20., 15.),
-            Restitution::coefficient(0.7),
-            ExternalImpulse::default(),
-            KinematicCharacterController::default(),
-        ))
-        .id();
-
-    let gun = commands
-        .spawn((
-            ShapeBundle {
-                path: GeometryBuilder::new().add(&gun).build(),
-                transform: Transform {
-                    translation: Vec3::new(0., 0., 0.1),
-                    ..default()
-                },
-                ..default()
-            },
-            Fill::color(Color::hex("bf3030").unwrap()),
-            Stroke::new(Color::hex("191919").unwrap(), 2.0),
-            ExampleShape,
-            Name::new("Player Gun"),
-            LookAt,
-        ))
-        .id();
-
-    let turret = commands
-        .spawn((
-            ShapeBundle {
-                path: GeometryBuilder::new().add(&turret).build(),
-                transform: Transform {
-                    translation: Vec3::new(0.0, 0.0, 0.2),
-                    ..default()
-                },
-                ..default()
-            },
-            Fill::color(Color::hex("bf3030").unwrap()),
-            Stroke::new(Color::hex("191919").unwrap(), 2.0),
-            ExampleShape,
-            Name::new("Player Turret"),
-        ))
-        .id();
-
-    commands.entity(body).add_child(gun);
-    commands.entity(gun).add_child(turret);
-
-    let joint_controller = RevoluteJointBuilder::new()
-        .local_anchor1(Vec2::new(10.0, 0.0))
-        .local_anchor2(Vec2::new(0.0, 0.0));
-
+fn spawn_player(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    loadout: &Loadout,
+    transform: Transform,
+) {
     commands.spawn((
-        Name::new("Player Controller"),
-        ImpulseJoint::new(body, joint_controller),
-        RigidBody::Dynamic,
-        Collider::ball(5.),
-        GlobalTransform::default(),
-        Transform {
-            translation: Vec3::new(10.0, 0.0, 0.0),
+        Player {
+            speed: 250.,
+            pull_distance: 10.,
             ..default()
         },
-        Velocity::default(),
-        PlayerPull { speed: 15000. },
-        Damping {
-            linear_damping: 1.0,
-            angular_damping: 100.,
+        FlowFieldGoal,
+        GameSessionEntity,
+        TankSpawnRequest {
+            blueprint: loadout.blueprint(asset_server),
+            team: Team::Red,
+            transform,
         },
-        ColliderMassProperties::Mass(0.5),
-        CollisionGroups::new(Group::NONE, Group::NONE),
     ));
 }
 
+fn spawn_ai_tank(commands: &mut Commands, asset_server: &AssetServer, spawn: &SpawnPoint) {
+    let mut tank = commands.spawn((
+        GameSessionEntity,
+        AiTankBundle::new(spawn.position),
+        TankSpawnRequest {
+            blueprint: TankBlueprint::Preset(asset_server.load("tanks/heavy.tank.ron")),
+            team: spawn.team,
+            transform: spawn.transform(),
+        },
+    ));
+
+    if spawn.team == Team::Blue {
+        tank.insert(Enemy);
+    }
+}
+
 const NUMBER_OF_ENEMIES: i32 = 300;
 
-pub fn spawn_enemies(mut commands: Commands, window_query: Query<&Window, With<PrimaryWindow>>) {
+/// Sessions with the same `LevelSeed` start with the enemies in the same places.
+pub fn spawn_enemies(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    seed: Res<LevelSeed>,
+) {
     for window in window_query.iter() {
+        let mut rng = StdRng::seed_from_u64(seed.0);
         let enemy = shapes::Circle {
             radius: 10.0,
             ..default()
@@ -172,9 +95,9 @@ pub fn spawn_enemies(mut commands: Commands, window_query: Query<&Window, With<P
 
         for _ in 0..NUMBER_OF_ENEMIES {
             let transform = Vec3::new(
-                (random::<f32>() * window.width()) - window.width() / 2.0,
-                (random::<f32>() * window.height()) - window.height() / 2.0,
-                random::<f32>(),
+                (rng.gen::<f32>() * window.width()) - window.width() / 2.0,
+                (rng.gen::<f32>() * window.height()) - window.height() / 2.0,
+                rng.gen::<f32>(),
             );
 
             commands.spawn((
@@ -187,13 +110,19 @@ pub fn spawn_enemies(mut commands: Commands, window_query: Query<&Window, With<P
                 Stroke::new(Color::hex("191919").unwrap(), 2.0),
                 ExampleShape,
                 Name::new("Enemy"),
+                Enemy,
+                GameSessionEntity,
+                Team::Blue,
+                Health::new(30.),
                 RigidBody::Dynamic,
                 Collider::ball(10.0),
                 Restitution::coefficient(0.7),
+                Velocity::default(),
                 Damping {
-                    linear_damping: 50.,
+                    linear_damping: 5.0,
                     angular_damping: 10.0,
                 },
+                FlowFieldAgent { speed: 120.0 },
             ));
         }
     }
@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::components::*;
+
+#[derive(Bundle)]
+pub struct ShellBundle {
+    #[bundle]
+    shape: ShapeBundle,
+    fill: Fill,
+    name: Name,
+    shell: Shell,
+    rigid_body: RigidBody,
+    collider: Collider,
+    velocity: Velocity,
+    ccd: Ccd,
+    active_events: ActiveEvents,
+}
+
+impl ShellBundle {
+    pub fn new(kind: ShellType, owner: Entity, position: Vec2, velocity: Vec2) -> Self {
+        let shell = shapes::Circle {
+            radius: kind.radius,
+            ..default()
+        };
+
+        Self {
+            shape: ShapeBundle {
+                path: GeometryBuilder::build_as(&shell),
+                transform: Transform::from_translation(position.extend(0.5)),
+                ..default()
+            },
+            fill: Fill::color(Color::hex("191919").unwrap()),
+            name: Name::new("Shell"),
+            shell: Shell {
+                owner,
+                kind,
+                lifetime: Timer::from_seconds(kind.lifetime, TimerMode::Once),
+                velocity,
+                last_position: position,
+            },
+            rigid_body: RigidBody::Dynamic,
+            collider: Collider::ball(kind.radius),
+            velocity: Velocity::linear(velocity),
+            ccd: Ccd::enabled(),
+            active_events: ActiveEvents::COLLISION_EVENTS,
+        }
+    }
+}
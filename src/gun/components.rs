@@ -0,0 +1,176 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::Deserialize;
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Component, Reflect, FromReflect, InspectorOptions, Deserialize,
+)]
+#[reflect(Component, PartialEq)]
+#[serde(default)]
+pub struct Gun {
+    /// Where shots leave the barrel, in the gun's local space.
+    pub muzzle_offset: Vec2,
+    pub weapon: Weapon,
+    /// Seconds between shots.
+    #[inspector(min = 0.0, max = 30.0)]
+    pub reload_time: f32,
+    /// Seconds until the gun can fire again.
+    #[inspector(min = 0.0, max = 30.0)]
+    pub reload_remaining: f32,
+    /// Seconds the trigger has been held while the gun was ready.
+    #[serde(skip)]
+    pub charge: f32,
+}
+
+impl Gun {
+    pub fn is_ready(&self) -> bool {
+        self.reload_remaining <= 0.0
+    }
+}
+
+impl Default for Gun {
+    fn default() -> Self {
+        Self {
+            muzzle_offset: Vec2::new(30.0, 0.0),
+            weapon: Weapon::default(),
+            reload_time: 1.5,
+            reload_remaining: 0.0,
+            charge: 0.0,
+        }
+    }
+}
+
+/// What a `Gun` does while its trigger is held.
+#[derive(Copy, Clone, Debug, PartialEq, Reflect, FromReflect, Deserialize)]
+#[reflect(PartialEq)]
+pub enum Weapon {
+    /// Fires a physical shell that is resolved against armor on impact.
+    Cannon {
+        muzzle_velocity: f32,
+        shell: ShellType,
+    },
+    /// Hitscan bullets, one per reload, scattered within `spread` degrees.
+    MachineGun {
+        damage: f32,
+        penetration: f32,
+        range: f32,
+        spread: f32,
+    },
+    /// Burns everything within `range` and `cone_angle` degrees of the barrel.
+    Flamethrower {
+        damage_per_second: f32,
+        penetration: f32,
+        range: f32,
+        cone_angle: f32,
+    },
+    /// Fires after the trigger is held for `charge_time` and hits every collider along the ray.
+    Railgun {
+        damage: f32,
+        penetration: f32,
+        range: f32,
+        charge_time: f32,
+    },
+}
+
+impl Default for Weapon {
+    fn default() -> Self {
+        Self::Cannon {
+            muzzle_velocity: 900.0,
+            shell: ShellType::default(),
+        }
+    }
+}
+
+/// Rotates its entity towards `target` at a limited rate, relative to the parent hull.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Component, Reflect, FromReflect, InspectorOptions, Deserialize,
+)]
+#[reflect(Component, PartialEq)]
+#[serde(default)]
+pub struct Turret {
+    /// Degrees per second.
+    #[inspector(min = 0.0, max = 720.0)]
+    pub traverse_rate: f32,
+    /// Limits of the traverse relative to the hull's forward direction.
+    pub arc: Option<TraverseArc>,
+    /// World position to aim at.
+    pub target: Option<Vec2>,
+    /// Signed angle in radians between where the turret points and where it should point.
+    pub aim_lag: f32,
+}
+
+impl Turret {
+    pub fn is_on_target(&self, tolerance: f32) -> bool {
+        self.target.is_some() && self.aim_lag.abs() <= tolerance
+    }
+}
+
+impl Default for Turret {
+    fn default() -> Self {
+        Self {
+            traverse_rate: 120.0,
+            arc: None,
+            target: None,
+            aim_lag: 0.0,
+        }
+    }
+}
+
+/// Traverse limits in degrees, counter-clockwise from the hull's forward direction.
+#[derive(Copy, Clone, Debug, PartialEq, Reflect, FromReflect, InspectorOptions, Deserialize)]
+#[reflect(PartialEq)]
+pub struct TraverseArc {
+    #[inspector(min = -180.0, max = 0.0)]
+    pub min: f32,
+    #[inspector(min = 0.0, max = 180.0)]
+    pub max: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Reflect, FromReflect, InspectorOptions, Deserialize)]
+#[reflect(PartialEq)]
+#[serde(default)]
+pub struct ShellType {
+    #[inspector(min = 0.5, max = 20.0)]
+    pub radius: f32,
+    #[inspector(min = 0.0, max = 10.0)]
+    pub lifetime: f32,
+    #[inspector(min = 0.0, max = 1000.0)]
+    pub damage: f32,
+    /// Armor thickness this shell defeats when hitting a face head-on.
+    #[inspector(min = 0.0, max = 1000.0)]
+    pub penetration: f32,
+}
+
+impl Default for ShellType {
+    fn default() -> Self {
+        Self {
+            radius: 3.0,
+            lifetime: 2.0,
+            damage: 25.0,
+            penetration: 90.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Component)]
+pub struct Shell {
+    /// The hull that fired this shell.
+    pub owner: Entity,
+    pub kind: ShellType,
+    pub lifetime: Timer,
+    /// Velocity at the moment of firing. Rapier may already have bounced the
+    /// shell when the impact is reported, so the impact direction is taken from here.
+    pub velocity: Vec2,
+    pub last_position: Vec2,
+}
+
+/// Draws the path of every shell in flight, and the cone of firing flamethrowers, with
+/// `DebugLines`.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShellTracing(pub bool);
+
+impl Default for ShellTracing {
+    fn default() -> Self {
+        Self(true)
+    }
+}
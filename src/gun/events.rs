@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+use super::components::ShellType;
+
+/// The trigger of the `Gun` entity `gun` is held this frame.
+#[derive(Debug, Clone, Copy)]
+pub struct FireGun {
+    pub gun: Entity,
+}
+
+/// A shell hit a collider.
+#[derive(Debug, Clone, Copy)]
+pub struct ShellHit {
+    /// The hull that fired the shell.
+    pub owner: Entity,
+    pub target: Entity,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    /// Outward surface normal of the target at the impact point.
+    pub normal: Vec2,
+    pub shell: ShellType,
+}
+
+/// A machine gun, flamethrower or railgun hit a collider.
+#[derive(Debug, Clone, Copy)]
+pub struct HitscanHit {
+    /// The hull that fired.
+    pub owner: Entity,
+    pub target: Entity,
+    pub direction: Vec2,
+    /// Outward surface normal of the target where it was hit.
+    pub normal: Vec2,
+    pub damage: f32,
+    pub penetration: f32,
+}
@@ -0,0 +1,44 @@
+pub mod bundles;
+pub mod components;
+pub mod events;
+mod systems;
+
+use bevy::prelude::*;
+
+use components::*;
+use events::*;
+use systems::*;
+
+use crate::GameplaySet;
+
+pub struct GunPlugin;
+
+impl Plugin for GunPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Gun>()
+            .register_type::<Weapon>()
+            .register_type::<ShellType>()
+            .register_type::<Turret>()
+            .init_resource::<ShellTracing>()
+            .add_event::<FireGun>()
+            .add_event::<ShellHit>()
+            .add_event::<HitscanHit>()
+            .add_system(traverse_turrets.in_set(GameplaySet))
+            .add_systems(
+                (
+                    fire_cannons,
+                    fire_machine_guns,
+                    fire_flamethrowers,
+                    fire_railguns,
+                )
+                    .after(reload_guns)
+                    .in_set(GameplaySet),
+            )
+            .add_system(reload_guns.in_set(GameplaySet))
+            .add_system(tick_shells.in_set(GameplaySet))
+            .add_system(shell_impacts.in_set(GameplaySet))
+            .add_system(shell_hit_impulse.after(shell_impacts).in_set(GameplaySet))
+            .add_system(trace_shells.in_set(GameplaySet))
+            .add_system(trace_flamethrowers.in_set(GameplaySet));
+    }
+}
@@ -0,0 +1,445 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::{prelude::*, utils::HashSet};
+use bevy_prototype_debug_lines::DebugLines;
+use bevy_rapier2d::prelude::*;
+
+use super::bundles::ShellBundle;
+use super::components::*;
+use super::events::*;
+use crate::combat::components::Health;
+use crate::GameSessionEntity;
+
+const SHELL_IMPULSE: f32 = 100.0;
+const SHELL_TRACE_DURATION: f32 = 0.5;
+const RAIL_TRACE_DURATION: f32 = 0.3;
+
+pub fn fire_cannons(
+    mut commands: Commands,
+    mut fire_events: EventReader<FireGun>,
+    mut gun_query: Query<(&mut Gun, &GlobalTransform, Option<&Parent>)>,
+) {
+    for event in fire_events.iter() {
+        if let Ok((mut gun, global_transform, parent)) = gun_query.get_mut(event.gun) {
+            let Weapon::Cannon {
+                muzzle_velocity,
+                shell,
+            } = gun.weapon
+            else {
+                continue;
+            };
+            if !gun.is_ready() {
+                continue;
+            }
+
+            gun.reload_remaining = gun.reload_time;
+
+            let owner = parent.map_or(event.gun, |parent| parent.get());
+            let (muzzle, direction) = muzzle(&gun, global_transform);
+
+            commands.spawn((
+                ShellBundle::new(shell, owner, muzzle, direction * muzzle_velocity),
+                GameSessionEntity,
+            ));
+        }
+    }
+}
+
+pub fn fire_machine_guns(
+    tracing: Res<ShellTracing>,
+    rapier_context: Res<RapierContext>,
+    mut lines: ResMut<DebugLines>,
+    mut fire_events: EventReader<FireGun>,
+    mut hit_events: EventWriter<HitscanHit>,
+    mut gun_query: Query<(&mut Gun, &GlobalTransform, Option<&Parent>)>,
+) {
+    for event in fire_events.iter() {
+        if let Ok((mut gun, global_transform, parent)) = gun_query.get_mut(event.gun) {
+            let Weapon::MachineGun {
+                damage,
+                penetration,
+                range,
+                spread,
+            } = gun.weapon
+            else {
+                continue;
+            };
+            if !gun.is_ready() {
+                continue;
+            }
+
+            gun.reload_remaining = gun.reload_time;
+
+            let owner = parent.map_or(event.gun, |parent| parent.get());
+            let (muzzle, direction) = muzzle(&gun, global_transform);
+            let scatter = (rand::random::<f32>() - 0.5) * spread.to_radians();
+            let direction = Vec2::from_angle(scatter).rotate(direction);
+
+            let end = match rapier_context.cast_ray_and_get_normal(
+                muzzle,
+                direction,
+                range,
+                true,
+                hit_filter(owner),
+            ) {
+                Some((target, intersection)) => {
+                    hit_events.send(HitscanHit {
+                        owner,
+                        target,
+                        direction,
+                        normal: intersection.normal,
+                        damage,
+                        penetration,
+                    });
+                    intersection.point
+                }
+                None => muzzle + direction * range,
+            };
+
+            if tracing.0 {
+                lines.line(muzzle.extend(0.), end.extend(0.), 0.0);
+            }
+        }
+    }
+}
+
+pub fn fire_flamethrowers(
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut fire_events: EventReader<FireGun>,
+    mut hit_events: EventWriter<HitscanHit>,
+    gun_query: Query<(&Gun, &GlobalTransform, Option<&Parent>)>,
+    target_query: Query<&GlobalTransform, With<Health>>,
+) {
+    for event in fire_events.iter() {
+        if let Ok((gun, global_transform, parent)) = gun_query.get(event.gun) {
+            let Weapon::Flamethrower {
+                damage_per_second,
+                penetration,
+                range,
+                cone_angle,
+            } = gun.weapon
+            else {
+                continue;
+            };
+
+            let owner = parent.map_or(event.gun, |parent| parent.get());
+            let (muzzle, direction) = muzzle(gun, global_transform);
+            let filter = hit_filter(owner);
+            let half_angle = cone_angle.to_radians() / 2.0;
+
+            let mut targets = Vec::new();
+            rapier_context.intersections_with_shape(
+                muzzle,
+                0.0,
+                &Collider::ball(range),
+                filter,
+                |target| {
+                    targets.push(target);
+                    true
+                },
+            );
+
+            for target in targets {
+                let Ok(target_transform) = target_query.get(target) else {
+                    continue;
+                };
+                let offset = target_transform.translation().truncate() - muzzle;
+                if direction.angle_between(offset).abs() > half_angle {
+                    continue;
+                }
+
+                // Flames do not go through walls.
+                let normal = match rapier_context
+                    .cast_ray_and_get_normal(muzzle, offset, 1.0, true, filter)
+                {
+                    Some((blocker, _)) if blocker != target => continue,
+                    Some((_, intersection)) => intersection.normal,
+                    None => -offset.normalize_or_zero(),
+                };
+
+                hit_events.send(HitscanHit {
+                    owner,
+                    target,
+                    direction: offset.normalize_or_zero(),
+                    normal,
+                    damage: damage_per_second * time.delta_seconds(),
+                    penetration,
+                });
+            }
+        }
+    }
+}
+
+/// Outlines the cone of every flamethrower that is firing.
+pub fn trace_flamethrowers(
+    tracing: Res<ShellTracing>,
+    mut lines: ResMut<DebugLines>,
+    mut fire_events: EventReader<FireGun>,
+    gun_query: Query<(&Gun, &GlobalTransform)>,
+) {
+    if !tracing.0 {
+        fire_events.clear();
+        return;
+    }
+
+    for event in fire_events.iter() {
+        if let Ok((gun, global_transform)) = gun_query.get(event.gun) {
+            let Weapon::Flamethrower {
+                range, cone_angle, ..
+            } = gun.weapon
+            else {
+                continue;
+            };
+
+            let (muzzle, direction) = muzzle(gun, global_transform);
+            let half_angle = cone_angle.to_radians() / 2.0;
+
+            for edge in [-half_angle, half_angle] {
+                let end = muzzle + Vec2::from_angle(edge).rotate(direction) * range;
+                lines.line(muzzle.extend(0.), end.extend(0.), 0.0);
+            }
+        }
+    }
+}
+
+/// Charges railguns while their trigger is held and fires them once fully charged.
+/// Letting go of the trigger drops the charge.
+pub fn fire_railguns(
+    time: Res<Time>,
+    tracing: Res<ShellTracing>,
+    rapier_context: Res<RapierContext>,
+    mut lines: ResMut<DebugLines>,
+    mut fire_events: EventReader<FireGun>,
+    mut hit_events: EventWriter<HitscanHit>,
+    mut gun_query: Query<(Entity, &mut Gun, &GlobalTransform, Option<&Parent>)>,
+) {
+    let triggered: HashSet<Entity> = fire_events.iter().map(|event| event.gun).collect();
+
+    for (entity, mut gun, global_transform, parent) in gun_query.iter_mut() {
+        let Weapon::Railgun {
+            damage,
+            penetration,
+            range,
+            charge_time,
+        } = gun.weapon
+        else {
+            continue;
+        };
+        if !triggered.contains(&entity) {
+            gun.charge = 0.0;
+            continue;
+        }
+        if !gun.is_ready() {
+            continue;
+        }
+
+        gun.charge += time.delta_seconds();
+        if gun.charge < charge_time {
+            continue;
+        }
+
+        gun.charge = 0.0;
+        gun.reload_remaining = gun.reload_time;
+
+        let owner = parent.map_or(entity, |parent| parent.get());
+        let (muzzle, direction) = muzzle(&gun, global_transform);
+
+        rapier_context.intersections_with_ray(
+            muzzle,
+            direction,
+            range,
+            true,
+            hit_filter(owner),
+            |target, intersection| {
+                hit_events.send(HitscanHit {
+                    owner,
+                    target,
+                    direction,
+                    normal: intersection.normal,
+                    damage,
+                    penetration,
+                });
+                true
+            },
+        );
+
+        if tracing.0 {
+            let end = muzzle + direction * range;
+            lines.line(muzzle.extend(0.), end.extend(0.), RAIL_TRACE_DURATION);
+        }
+    }
+}
+
+/// World position of the muzzle and the direction the barrel points in.
+fn muzzle(gun: &Gun, global_transform: &GlobalTransform) -> (Vec2, Vec2) {
+    let (_, rotation, _) = global_transform.to_scale_rotation_translation();
+    let direction = (rotation * Vec3::X).truncate().normalize_or_zero();
+    let muzzle = global_transform
+        .transform_point(gun.muzzle_offset.extend(0.0))
+        .truncate();
+
+    (muzzle, direction)
+}
+
+/// Hitscan weapons hit everything solid except the hull that fired them.
+fn hit_filter(owner: Entity) -> QueryFilter<'static> {
+    QueryFilter::new()
+        .exclude_rigid_body(owner)
+        .exclude_sensors()
+}
+
+pub fn reload_guns(time: Res<Time>, mut gun_query: Query<&mut Gun>) {
+    for mut gun in gun_query.iter_mut() {
+        if !gun.is_ready() {
+            gun.reload_remaining = (gun.reload_remaining - time.delta_seconds()).max(0.0);
+        }
+    }
+}
+
+pub fn traverse_turrets(
+    time: Res<Time>,
+    mut turret_query: Query<(&mut Turret, &mut Transform, &GlobalTransform, &Parent)>,
+    hull_query: Query<&GlobalTransform, Without<Turret>>,
+) {
+    for (mut turret, mut transform, global_transform, parent) in turret_query.iter_mut() {
+        let (Some(target), Ok(hull_transform)) = (turret.target, hull_query.get(parent.get()))
+        else {
+            turret.aim_lag = 0.0;
+            continue;
+        };
+
+        let hull_heading = heading(hull_transform.to_scale_rotation_translation().1);
+        let direction = target - global_transform.translation().truncate();
+        let desired = direction.y.atan2(direction.x);
+
+        let current_local = heading(transform.rotation);
+        let mut desired_local = wrap_angle(desired - hull_heading);
+        let delta = match turret.arc {
+            Some(arc) => {
+                // Inside an arc the turret must not swing through the blocked sector.
+                desired_local = desired_local.clamp(arc.min.to_radians(), arc.max.to_radians());
+                desired_local - current_local
+            }
+            None => wrap_angle(desired_local - current_local),
+        };
+
+        let max_step = turret.traverse_rate.to_radians() * time.delta_seconds();
+        let local = current_local + delta.clamp(-max_step, max_step);
+
+        transform.rotation = Quat::from_rotation_z(local);
+        turret.aim_lag = wrap_angle(desired - (hull_heading + local));
+    }
+}
+
+fn heading(rotation: Quat) -> f32 {
+    let forward = rotation * Vec3::X;
+    forward.y.atan2(forward.x)
+}
+
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+pub fn tick_shells(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut shell_query: Query<(Entity, &mut Shell)>,
+) {
+    for (entity, mut shell) in shell_query.iter_mut() {
+        if shell.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn shell_impacts(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut hit_events: EventWriter<ShellHit>,
+    rapier_context: Res<RapierContext>,
+    shell_query: Query<(&Shell, &Transform)>,
+    sensor_query: Query<(), With<Sensor>>,
+) {
+    let mut spent = HashSet::new();
+
+    for collision_event in collision_events.iter() {
+        if let CollisionEvent::Started(entity1, entity2, _) = *collision_event {
+            for (shell_entity, target) in [(entity1, entity2), (entity2, entity1)] {
+                if spent.contains(&shell_entity) {
+                    continue;
+                }
+
+                if let Ok((shell, transform)) = shell_query.get(shell_entity) {
+                    // Shells fly over water and pickups.
+                    if target == shell.owner || sensor_query.contains(target) {
+                        continue;
+                    }
+
+                    spent.insert(shell_entity);
+                    hit_events.send(ShellHit {
+                        owner: shell.owner,
+                        target,
+                        position: transform.translation.truncate(),
+                        velocity: shell.velocity,
+                        normal: contact_normal(&rapier_context, target, shell_entity)
+                            .unwrap_or(-shell.velocity.normalize_or_zero()),
+                        shell: shell.kind,
+                    });
+                    commands.entity(shell_entity).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+/// World-space normal of `target`'s surface, pointing towards `other`.
+fn contact_normal(rapier_context: &RapierContext, target: Entity, other: Entity) -> Option<Vec2> {
+    let contact_pair = rapier_context.contact_pair(target, other)?;
+    let manifold = contact_pair.manifolds().next()?;
+
+    // Rapier's normal points from the first collider of the pair to the second.
+    if contact_pair.collider1() == target {
+        Some(manifold.normal())
+    } else {
+        Some(-manifold.normal())
+    }
+}
+
+pub fn shell_hit_impulse(
+    mut commands: Commands,
+    mut hit_events: EventReader<ShellHit>,
+    target_query: Query<&Transform, With<RigidBody>>,
+) {
+    for hit in hit_events.iter() {
+        if let Ok(transform) = target_query.get(hit.target) {
+            commands
+                .entity(hit.target)
+                .insert(ExternalImpulse::at_point(
+                    hit.velocity.normalize_or_zero() * SHELL_IMPULSE,
+                    hit.position,
+                    transform.translation.truncate(),
+                ));
+        }
+    }
+}
+
+pub fn trace_shells(
+    tracing: Res<ShellTracing>,
+    mut lines: ResMut<DebugLines>,
+    mut shell_query: Query<(&mut Shell, &Transform)>,
+) {
+    for (mut shell, transform) in shell_query.iter_mut() {
+        let position = transform.translation.truncate();
+
+        if tracing.0 {
+            lines.line(
+                shell.last_position.extend(0.),
+                position.extend(0.),
+                SHELL_TRACE_DURATION,
+            );
+        }
+
+        shell.last_position = position;
+    }
+}
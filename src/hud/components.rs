@@ -0,0 +1,7 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct Hud {}
+
+#[derive(Component)]
+pub struct SeedLabel {}
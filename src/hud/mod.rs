@@ -0,0 +1,17 @@
+mod components;
+pub mod styles;
+mod systems;
+
+use bevy::prelude::*;
+
+use systems::*;
+
+use crate::AppState;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(spawn_hud.in_schedule(OnEnter(AppState::Game)));
+    }
+}
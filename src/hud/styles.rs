@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+pub const HUD_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    position: UiRect {
+        left: Val::Px(8.0),
+        top: Val::Px(8.0),
+        ..UiRect::DEFAULT
+    },
+    flex_direction: FlexDirection::Column,
+    ..Style::DEFAULT
+};
+
+pub fn get_hud_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 20.0,
+        color: Color::WHITE,
+    }
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use crate::hud::components::*;
+use crate::hud::styles::*;
+use crate::level::{
+    components::{LevelSeed, SelectedLevel},
+    generator::PROCEDURAL_LEVEL_NAME,
+};
+use crate::GameSessionEntity;
+
+pub fn spawn_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    selected_level: Res<SelectedLevel>,
+    seed: Res<LevelSeed>,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: HUD_STYLE,
+                ..default()
+            },
+            Hud {},
+            GameSessionEntity,
+            Name::new("Hud"),
+        ))
+        .with_children(|parent| {
+            // Only generated levels depend on the seed.
+            if selected_level.0 == PROCEDURAL_LEVEL_NAME {
+                parent.spawn((
+                    TextBundle::from_section(
+                        format!("Seed: {}", seed.0),
+                        get_hud_text_style(&asset_server),
+                    ),
+                    SeedLabel {},
+                ));
+            }
+        });
+}
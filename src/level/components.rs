@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+use super::definition::*;
+use super::generator::PROCEDURAL_LEVEL_NAME;
+
+/// Root of a spawned level. Walls, water, props and pickups are its children.
+#[derive(Component)]
+pub struct Level;
+
+#[derive(Component)]
+pub struct Wall;
+
+/// Slows down tanks driving through it.
+#[derive(Component)]
+pub struct Water;
+
+#[derive(Component)]
+pub struct Prop;
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Pickup {
+    pub kind: PickupKind,
+}
+
+/// Builds the level named `name` on its entity as soon as it has finished loading.
+#[derive(Component, Clone, Debug)]
+pub struct LevelSpawnRequest {
+    pub name: String,
+}
+
+/// Every level found in `assets/levels`, plus the last generated one.
+#[derive(Resource, Default)]
+pub struct LevelLibrary {
+    pub levels: Vec<Handle<LevelAsset>>,
+    pub generated: Option<Handle<LevelAsset>>,
+}
+
+impl LevelLibrary {
+    pub fn get<'a>(&self, levels: &'a Assets<LevelAsset>, name: &str) -> Option<&'a LevelAsset> {
+        self.levels
+            .iter()
+            .chain(self.generated.iter())
+            .filter_map(|handle| levels.get(handle))
+            .find(|level| level.name == name)
+    }
+
+    /// The name of the level after `current` in alphabetical order.
+    pub fn next_name(&self, levels: &Assets<LevelAsset>, current: &str) -> Option<String> {
+        let mut names: Vec<&str> = self
+            .levels
+            .iter()
+            .filter_map(|handle| levels.get(handle))
+            .map(|level| level.name.as_str())
+            .collect();
+        names.push(PROCEDURAL_LEVEL_NAME);
+        names.sort();
+
+        let next = names
+            .iter()
+            .position(|name| *name == current)
+            .map_or(0, |index| (index + 1) % names.len());
+
+        names.get(next).map(|name| name.to_string())
+    }
+}
+
+/// Name of the level played when entering `AppState::Game`.
+#[derive(Resource, Clone, Debug, PartialEq, Eq)]
+pub struct SelectedLevel(pub String);
+
+impl Default for SelectedLevel {
+    fn default() -> Self {
+        Self("Arena".to_string())
+    }
+}
+
+/// Seed of the procedural level. The enemy swarm is placed from it too, so entering
+/// the same seed replays the same session.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LevelSeed(pub u64);
+
+impl Default for LevelSeed {
+    fn default() -> Self {
+        Self(rand::random())
+    }
+}
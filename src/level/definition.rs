@@ -0,0 +1,106 @@
+use bevy::{prelude::*, reflect::TypeUuid};
+use serde::{Deserialize, Serialize};
+
+use crate::combat::components::Destructible;
+use crate::tank::components::Team;
+
+/// A map from `assets/levels/*.level.ron` or `assets/levels/*.level.svg`.
+///
+/// All positions are in pixels.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TypeUuid)]
+#[uuid = "9a3e7c52-61d4-4b0f-a8d2-2f5e4c1b7d93"]
+pub struct LevelAsset {
+    /// Shown in the menu and used to select the level.
+    pub name: String,
+    #[serde(default)]
+    pub walls: Vec<WallDefinition>,
+    #[serde(default)]
+    pub spawns: Vec<SpawnPoint>,
+    #[serde(default)]
+    pub water: Vec<WaterDefinition>,
+    #[serde(default)]
+    pub pickups: Vec<PickupDefinition>,
+    /// Decorative shapes. Only destructible ones get a collider.
+    #[serde(default)]
+    pub props: Vec<PropDefinition>,
+}
+
+/// A static wall. Closed polygons may be concave.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WallDefinition {
+    pub points: Vec<Vec2>,
+    #[serde(default = "default_wall_color")]
+    pub color: String,
+    /// Open walls are thin lines through `points`, like a fence, instead of a filled polygon.
+    #[serde(default)]
+    pub open: bool,
+    /// Closed walls only.
+    #[serde(default)]
+    pub destructible: Option<Destructible>,
+}
+
+/// Shallow water that tanks can cross, but only slowly. Shells fly over it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaterDefinition {
+    pub points: Vec<Vec2>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpawnPoint {
+    pub team: Team,
+    pub position: Vec2,
+    /// Degrees counter-clockwise from +X.
+    #[serde(default)]
+    pub rotation: f32,
+}
+
+impl SpawnPoint {
+    pub fn transform(&self) -> Transform {
+        Transform::from_translation(self.position.extend(0.0))
+            .with_rotation(Quat::from_rotation_z(self.rotation.to_radians()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PickupDefinition {
+    pub kind: PickupKind,
+    pub position: Vec2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PickupKind {
+    /// Restores this much health to the tank that drives over it.
+    Repair(f32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropDefinition {
+    pub points: Vec<Vec2>,
+    pub color: String,
+    /// Draw order relative to the ground. Negative values are drawn below tanks.
+    #[serde(default = "default_prop_z")]
+    pub z: f32,
+    /// Destructible props get a collider so they can be shot.
+    #[serde(default)]
+    pub destructible: Option<Destructible>,
+}
+
+impl WallDefinition {
+    pub fn color(&self) -> Color {
+        Color::hex(&self.color).unwrap_or(Color::GRAY)
+    }
+}
+
+impl PropDefinition {
+    pub fn color(&self) -> Color {
+        Color::hex(&self.color).unwrap_or(Color::GRAY)
+    }
+}
+
+pub(crate) fn default_wall_color() -> String {
+    "595959".to_string()
+}
+
+pub(crate) fn default_prop_z() -> f32 {
+    -1.0
+}
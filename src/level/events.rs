@@ -0,0 +1,7 @@
+use super::definition::SpawnPoint;
+
+/// The level's geometry exists and tanks can be placed on its spawn points.
+#[derive(Debug, Clone)]
+pub struct LevelSpawned {
+    pub spawns: Vec<SpawnPoint>,
+}
@@ -0,0 +1,362 @@
+//! Cave levels grown from a seed with a cellular automaton.
+//!
+//! The map starts as random noise, is smoothed into caves and then every cave but the
+//! largest is filled in, so all spawn points are always connected.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+use super::definition::*;
+use crate::tank::components::Team;
+
+/// Name of the generated level in the level list.
+pub const PROCEDURAL_LEVEL_NAME: &str = "Procedural";
+
+const COLUMNS: usize = 48;
+const ROWS: usize = 30;
+const CELL_SIZE: f32 = 40.0;
+const WALL_CHANCE: f64 = 0.45;
+const SMOOTHING_STEPS: usize = 5;
+/// Caves whose open area covers less than this share of the map are grown again.
+const MIN_OPEN_SHARE: f32 = 0.4;
+const SPAWNS_PER_TEAM: usize = 2;
+/// Spawn points of one team are at least this many cells apart.
+const SPAWN_SPACING: usize = 4;
+const WALL_COLOR: &str = "4d4d40";
+const REPAIR_AMOUNT: f32 = 50.0;
+
+/// Builds the same level for the same `seed`.
+pub fn generate_level(seed: u64) -> LevelAsset {
+    let cave = Cave::grow(seed);
+
+    let mut spawns = Vec::new();
+    for team in [Team::Red, Team::Blue] {
+        let rotation = if team == Team::Red { 0.0 } else { 180.0 };
+        spawns.extend(cave.spawn_cells(team).into_iter().map(|cell| SpawnPoint {
+            team,
+            position: cell_center(cell),
+            rotation,
+        }));
+    }
+
+    let pickups = cave
+        .open_cells()
+        .min_by_key(|cell| {
+            let center = UVec2::new(COLUMNS as u32, ROWS as u32) / 2;
+            cell.x.abs_diff(center.x) + cell.y.abs_diff(center.y)
+        })
+        .map(|cell| PickupDefinition {
+            kind: PickupKind::Repair(REPAIR_AMOUNT),
+            position: cell_center(cell),
+        })
+        .into_iter()
+        .collect();
+
+    LevelAsset {
+        name: PROCEDURAL_LEVEL_NAME.to_string(),
+        walls: cave
+            .wall_rects()
+            .into_iter()
+            .map(|(min, max)| {
+                let min = cell_corner(min);
+                let max = cell_corner(max);
+                WallDefinition {
+                    points: vec![min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)],
+                    color: WALL_COLOR.to_string(),
+                    open: false,
+                    destructible: None,
+                }
+            })
+            .collect(),
+        spawns,
+        water: Vec::new(),
+        pickups,
+        props: Vec::new(),
+    }
+}
+
+/// The map is centred on the origin.
+fn cell_corner(cell: UVec2) -> Vec2 {
+    (cell.as_vec2() - Vec2::new(COLUMNS as f32, ROWS as f32) / 2.0) * CELL_SIZE
+}
+
+fn cell_center(cell: UVec2) -> Vec2 {
+    cell_corner(cell) + Vec2::splat(CELL_SIZE / 2.0)
+}
+
+struct Cave {
+    walls: Vec<bool>,
+}
+
+impl Cave {
+    fn grow(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        loop {
+            let mut cave = Self {
+                walls: (0..COLUMNS * ROWS)
+                    .map(|index| {
+                        let (x, y) = (index % COLUMNS, index / COLUMNS);
+                        Self::is_border(x, y) || rng.gen_bool(WALL_CHANCE)
+                    })
+                    .collect(),
+            };
+
+            for _ in 0..SMOOTHING_STEPS {
+                cave.smooth();
+            }
+            cave.fill_all_but_largest_region();
+
+            let open = cave.walls.iter().filter(|wall| !**wall).count();
+            if open as f32 >= MIN_OPEN_SHARE * cave.walls.len() as f32 {
+                return cave;
+            }
+        }
+    }
+
+    fn is_border(x: usize, y: usize) -> bool {
+        x == 0 || y == 0 || x == COLUMNS - 1 || y == ROWS - 1
+    }
+
+    /// Cells outside the map count as walls.
+    fn is_wall(&self, x: isize, y: isize) -> bool {
+        if x < 0 || y < 0 || x >= COLUMNS as isize || y >= ROWS as isize {
+            return true;
+        }
+        self.walls[y as usize * COLUMNS + x as usize]
+    }
+
+    fn walls_around(&self, x: usize, y: usize, radius: isize) -> usize {
+        let (x, y) = (x as isize, y as isize);
+        (-radius..=radius)
+            .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| (dx, dy) != (0, 0) && self.is_wall(x + dx, y + dy))
+            .count()
+    }
+
+    /// A cell becomes a wall when most of its neighbours are walls.
+    fn smooth(&mut self) {
+        self.walls = (0..COLUMNS * ROWS)
+            .map(|index| {
+                let (x, y) = (index % COLUMNS, index / COLUMNS);
+                match self.walls_around(x, y, 1) {
+                    _ if Self::is_border(x, y) => true,
+                    count if count > 4 => true,
+                    count if count < 4 => false,
+                    _ => self.walls[index],
+                }
+            })
+            .collect();
+    }
+
+    /// Open cells reachable from `start`, including it.
+    fn region(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.walls.len()];
+        let mut queue = VecDeque::from([start]);
+        let mut region = Vec::new();
+        visited[start] = true;
+
+        while let Some(index) = queue.pop_front() {
+            region.push(index);
+            let (x, y) = ((index % COLUMNS) as isize, (index / COLUMNS) as isize);
+
+            for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if !self.is_wall(nx, ny) {
+                    let neighbour = ny as usize * COLUMNS + nx as usize;
+                    if !visited[neighbour] {
+                        visited[neighbour] = true;
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+        }
+
+        region
+    }
+
+    fn fill_all_but_largest_region(&mut self) {
+        let mut seen = vec![false; self.walls.len()];
+        let mut largest = Vec::new();
+
+        for index in 0..self.walls.len() {
+            if self.walls[index] || seen[index] {
+                continue;
+            }
+
+            let region = self.region(index);
+            for &cell in &region {
+                seen[cell] = true;
+            }
+            if region.len() > largest.len() {
+                largest = region;
+            }
+        }
+
+        let mut walls = vec![true; self.walls.len()];
+        for index in largest {
+            walls[index] = false;
+        }
+        self.walls = walls;
+    }
+
+    fn open_cells(&self) -> impl Iterator<Item = UVec2> + '_ {
+        self.walls
+            .iter()
+            .enumerate()
+            .filter(|(_, wall)| !**wall)
+            .map(|(index, _)| UVec2::new((index % COLUMNS) as u32, (index / COLUMNS) as u32))
+    }
+
+    /// Red spawns as far left and blue as far right as there is room for a tank.
+    fn spawn_cells(&self, team: Team) -> Vec<UVec2> {
+        let mut candidates: Vec<UVec2> = self
+            .open_cells()
+            .filter(|cell| self.walls_around(cell.x as usize, cell.y as usize, 1) == 0)
+            .collect();
+        if candidates.is_empty() {
+            candidates = self.open_cells().collect();
+        }
+
+        let middle = ROWS as u32 / 2;
+        candidates.sort_by_key(|cell| {
+            let from_edge = match team {
+                Team::Red => cell.x,
+                Team::Blue => COLUMNS as u32 - 1 - cell.x,
+            };
+            (from_edge, cell.y.abs_diff(middle))
+        });
+
+        let mut spawns: Vec<UVec2> = Vec::new();
+        for cell in candidates {
+            let spaced = spawns.iter().all(|spawn| {
+                spawn.x.abs_diff(cell.x).max(spawn.y.abs_diff(cell.y)) as usize >= SPAWN_SPACING
+            });
+            if spaced {
+                spawns.push(cell);
+                if spawns.len() == SPAWNS_PER_TEAM {
+                    break;
+                }
+            }
+        }
+
+        spawns
+    }
+
+    /// Wall cells merged into as few rectangles as greedily possible, as
+    /// `(min, max)` cell corners.
+    fn wall_rects(&self) -> Vec<(UVec2, UVec2)> {
+        let mut covered = vec![false; self.walls.len()];
+        let free = |covered: &[bool], x: usize, y: usize| {
+            self.walls[y * COLUMNS + x] && !covered[y * COLUMNS + x]
+        };
+        let mut rects = Vec::new();
+
+        for y in 0..ROWS {
+            for x in 0..COLUMNS {
+                if !free(&covered, x, y) {
+                    continue;
+                }
+
+                let mut width = 1;
+                while x + width < COLUMNS && free(&covered, x + width, y) {
+                    width += 1;
+                }
+
+                let mut height = 1;
+                while y + height < ROWS
+                    && (x..x + width).all(|column| free(&covered, column, y + height))
+                {
+                    height += 1;
+                }
+
+                for row in y..y + height {
+                    for column in x..x + width {
+                        covered[row * COLUMNS + column] = true;
+                    }
+                }
+
+                rects.push((
+                    UVec2::new(x as u32, y as u32),
+                    UVec2::new((x + width) as u32, (y + height) as u32),
+                ));
+            }
+        }
+
+        rects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_builds_same_level() {
+        let a = generate_level(42);
+        let b = generate_level(42);
+
+        assert_eq!(a.spawns, b.spawns);
+        assert_eq!(a.walls.len(), b.walls.len());
+        assert!(a
+            .walls
+            .iter()
+            .zip(b.walls.iter())
+            .all(|(a, b)| a.points == b.points));
+    }
+
+    #[test]
+    fn every_team_gets_its_spawns() {
+        for seed in 0..20 {
+            let level = generate_level(seed);
+
+            for team in [Team::Red, Team::Blue] {
+                let count = level
+                    .spawns
+                    .iter()
+                    .filter(|spawn| spawn.team == team)
+                    .count();
+                assert_eq!(count, SPAWNS_PER_TEAM, "seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn spawns_are_connected() {
+        for seed in 0..20 {
+            let cave = Cave::grow(seed);
+            let spawns: Vec<UVec2> = [Team::Red, Team::Blue]
+                .into_iter()
+                .flat_map(|team| cave.spawn_cells(team))
+                .collect();
+
+            let start = spawns[0].y as usize * COLUMNS + spawns[0].x as usize;
+            let region = cave.region(start);
+            assert!(
+                spawns
+                    .iter()
+                    .all(|cell| region.contains(&(cell.y as usize * COLUMNS + cell.x as usize))),
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn wall_rects_cover_exactly_the_walls() {
+        let cave = Cave::grow(7);
+        let mut covered = vec![false; cave.walls.len()];
+
+        for (min, max) in cave.wall_rects() {
+            for y in min.y..max.y {
+                for x in min.x..max.x {
+                    let index = y as usize * COLUMNS + x as usize;
+                    assert!(!covered[index]);
+                    covered[index] = true;
+                }
+            }
+        }
+
+        assert_eq!(covered, cave.walls);
+    }
+}
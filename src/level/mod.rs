@@ -0,0 +1,36 @@
+pub mod components;
+pub mod definition;
+pub mod events;
+pub mod generator;
+mod svg;
+mod systems;
+
+use bevy::prelude::*;
+
+use components::*;
+use definition::*;
+use events::*;
+use svg::*;
+use systems::*;
+
+use crate::tank::definition::RonAssetLoader;
+use crate::{AppState, GameplaySet};
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelLibrary>()
+            .init_resource::<SelectedLevel>()
+            .init_resource::<LevelSeed>()
+            .add_asset::<LevelAsset>()
+            .add_asset_loader(RonAssetLoader::<LevelAsset>::new(&["level.ron"]))
+            .add_asset_loader(SvgLevelLoader)
+            .add_event::<LevelSpawned>()
+            .add_startup_system(load_levels)
+            .add_system(request_selected_level.in_schedule(OnEnter(AppState::Game)))
+            .add_system(spawn_requested_levels)
+            .add_system(collect_pickups.in_set(GameplaySet))
+            .add_system(wade_through_water.in_set(GameplaySet));
+    }
+}
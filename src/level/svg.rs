@@ -0,0 +1,498 @@
+//! Levels drawn in Inkscape, from `assets/levels/*.level.svg`.
+//!
+//! Every shape is sorted into a part of the level by its `id`, its `class` or the
+//! `id`/label of a group or layer it sits in, whichever matches first going outwards:
+//!
+//! - `wall`: closed shapes become solid walls, open paths become thin walls like fences.
+//! - `water`: shallow water that slows tanks down.
+//! - `spawn-red`, `spawn-blue`: a spawn point at the centre of the shape. For an open path
+//!   the tank stands at its start and faces its end.
+//! - `repair`: a repair pickup at the centre of the shape.
+//! - anything else with a fill is a decorative prop.
+//!
+//! Closed walls and props with the class `destructible` can be shot to pieces.
+//!
+//! A name matches when it is the role itself, its plural, or the role followed by `-`, `_` or
+//! a space, so `wall-3` and a layer labelled `Walls` both hold walls.
+//!
+//! One SVG user unit is one pixel. The centre of the `viewBox` becomes the level origin and
+//! +Y points up like in the rest of the game.
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    math::Affine2,
+    prelude::*,
+    utils::BoxedFuture,
+};
+use lyon::extra::parser::{ParserOptions, PathParser, Source};
+use lyon::path::{
+    math::{point, vector, Angle, Box2D},
+    Path, PathEvent, Polygon, Winding,
+};
+use roxmltree::{Document, Node};
+
+use super::definition::*;
+use crate::combat::components::Destructible;
+use crate::tank::components::Team;
+
+const INKSCAPE_NS: &str = "http://www.inkscape.org/namespaces/inkscape";
+
+/// Largest distance between a curve and the line segments it is flattened into.
+const FLATTEN_TOLERANCE: f32 = 0.5;
+
+/// Health restored by `repair` pickups. The SVG has nowhere to put the amount.
+const REPAIR_AMOUNT: f32 = 50.0;
+const DESTRUCTIBLE_HEALTH: f32 = 200.0;
+
+/// Elements whose children are never drawn directly.
+const NOT_RENDERED: &[&str] = &[
+    "defs", "clipPath", "mask", "marker", "pattern", "symbol", "metadata",
+];
+
+pub struct SvgLevelLoader;
+
+impl AssetLoader for SvgLevelLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let file_name = load_context
+                .path()
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            let fallback_name = file_name.split('.').next().unwrap_or(file_name);
+
+            let level = parse_svg_level(std::str::from_utf8(bytes)?, fallback_name)?;
+            load_context.set_default_asset(LoadedAsset::new(level));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.svg"]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Role {
+    Wall,
+    Water,
+    Spawn(Team),
+    Repair,
+    Prop,
+}
+
+impl Role {
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_lowercase();
+        let is = |role: &str| {
+            name.strip_prefix(role).is_some_and(|rest| {
+                rest.is_empty() || rest == "s" || rest.starts_with(['-', '_', ' '])
+            })
+        };
+
+        if is("spawn-red") {
+            Some(Self::Spawn(Team::Red))
+        } else if is("spawn-blue") {
+            Some(Self::Spawn(Team::Blue))
+        } else if is("wall") {
+            Some(Self::Wall)
+        } else if is("water") {
+            Some(Self::Water)
+        } else if is("repair") {
+            Some(Self::Repair)
+        } else if is("prop") {
+            Some(Self::Prop)
+        } else {
+            None
+        }
+    }
+
+    fn of(node: Node) -> Self {
+        node.ancestors()
+            .filter(Node::is_element)
+            .find_map(|node| {
+                let id = node.attribute("id");
+                let label = node.attribute((INKSCAPE_NS, "label"));
+                let classes = node.attribute("class").unwrap_or_default();
+
+                id.into_iter()
+                    .chain(classes.split_whitespace())
+                    .chain(label)
+                    .find_map(Self::from_name)
+            })
+            .unwrap_or(Self::Prop)
+    }
+}
+
+/// One flattened subpath of a shape, in level space.
+struct Outline {
+    points: Vec<Vec2>,
+    closed: bool,
+}
+
+impl Outline {
+    fn center(&self) -> Vec2 {
+        let min = self
+            .points
+            .iter()
+            .copied()
+            .reduce(Vec2::min)
+            .unwrap_or_default();
+        let max = self
+            .points
+            .iter()
+            .copied()
+            .reduce(Vec2::max)
+            .unwrap_or_default();
+        (min + max) / 2.0
+    }
+}
+
+/// Builds a level out of the shapes in `svg`. `fallback_name` is used when the
+/// document has no `<title>`.
+pub fn parse_svg_level(svg: &str, fallback_name: &str) -> Result<LevelAsset, bevy::asset::Error> {
+    let document = Document::parse(svg)?;
+    let root = document.root_element();
+
+    let name = root
+        .children()
+        .find(|node| node.has_tag_name("title"))
+        .and_then(|node| node.text())
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .unwrap_or(fallback_name);
+
+    let mut level = LevelAsset {
+        name: name.to_string(),
+        walls: Vec::new(),
+        spawns: Vec::new(),
+        water: Vec::new(),
+        pickups: Vec::new(),
+        props: Vec::new(),
+    };
+
+    let to_level = Affine2::from_scale(Vec2::new(1.0, -1.0))
+        * Affine2::from_translation(-view_box_center(root));
+
+    for node in root.descendants().filter(|node| is_drawn(*node)) {
+        let Some(path) = shape_path(node)? else {
+            continue;
+        };
+
+        let transform = to_level * node_transform(node)?;
+        let role = Role::of(node);
+        let destructible = node
+            .attribute("class")
+            .unwrap_or_default()
+            .split_whitespace()
+            .any(|class| class == "destructible")
+            .then_some(Destructible {
+                health: DESTRUCTIBLE_HEALTH,
+                fracture: true,
+            });
+
+        for outline in outlines(&path, transform) {
+            match role {
+                Role::Wall => level.walls.push(WallDefinition {
+                    points: outline.points,
+                    color: paint(node, "fill")
+                        .or_else(|| paint(node, "stroke"))
+                        .unwrap_or_else(default_wall_color),
+                    open: !outline.closed,
+                    destructible: destructible.filter(|_| outline.closed),
+                }),
+                Role::Water => level.water.push(WaterDefinition {
+                    points: outline.points,
+                }),
+                Role::Spawn(team) => {
+                    let spawn = match outline.points.as_slice() {
+                        [start, .., end] if !outline.closed => SpawnPoint {
+                            team,
+                            position: *start,
+                            rotation: Vec2::X.angle_between(*end - *start).to_degrees(),
+                        },
+                        _ => SpawnPoint {
+                            team,
+                            position: outline.center(),
+                            rotation: 0.0,
+                        },
+                    };
+                    level.spawns.push(spawn);
+                }
+                Role::Repair => level.pickups.push(PickupDefinition {
+                    kind: PickupKind::Repair(REPAIR_AMOUNT),
+                    position: outline.center(),
+                }),
+                Role::Prop => {
+                    if let Some(color) = paint(node, "fill") {
+                        level.props.push(PropDefinition {
+                            points: outline.points,
+                            color,
+                            z: default_prop_z(),
+                            destructible,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(level)
+}
+
+/// Shapes that are visible and not inside `<defs>` and the like.
+fn is_drawn(node: Node) -> bool {
+    node.is_element()
+        && node.ancestors().filter(Node::is_element).all(|node| {
+            !NOT_RENDERED.contains(&node.tag_name().name())
+                && node.attribute("display") != Some("none")
+                && style_property(node, "display") != Some("none")
+        })
+}
+
+fn view_box_center(root: Node) -> Vec2 {
+    let numbers = root
+        .attribute("viewBox")
+        .map(parse_numbers)
+        .unwrap_or_default();
+
+    match numbers.as_slice() {
+        [x, y, width, height] => Vec2::new(x + width / 2.0, y + height / 2.0),
+        _ => Vec2::ZERO,
+    }
+}
+
+/// The combined `transform` of `node` and all its ancestors.
+fn node_transform(node: Node) -> Result<Affine2, bevy::asset::Error> {
+    let mut transform = Affine2::IDENTITY;
+
+    for node in node.ancestors().filter(Node::is_element) {
+        if let Some(text) = node.attribute("transform") {
+            let t: svgtypes::Transform = text.parse()?;
+            let local = Affine2::from_cols_array(&[
+                t.a as f32, t.b as f32, t.c as f32, t.d as f32, t.e as f32, t.f as f32,
+            ]);
+            transform = local * transform;
+        }
+    }
+
+    Ok(transform)
+}
+
+/// The flattened geometry of `node` in its own coordinates, if it is a shape.
+fn shape_path(node: Node) -> Result<Option<Path>, bevy::asset::Error> {
+    let number = |name: &str| {
+        node.attribute(name)
+            .and_then(|value| value.trim_end_matches("px").parse::<f32>().ok())
+            .unwrap_or(0.0)
+    };
+    let mut builder = Path::builder().flattened(FLATTEN_TOLERANCE);
+
+    match node.tag_name().name() {
+        "path" => {
+            let Some(data) = node.attribute("d") else {
+                return Ok(None);
+            };
+            PathParser::new().parse(
+                &ParserOptions::DEFAULT,
+                &mut Source::new(data.chars()),
+                &mut builder,
+            )?;
+        }
+        "rect" => {
+            let min = point(number("x"), number("y"));
+            builder.add_rectangle(
+                &Box2D::new(min, min + vector(number("width"), number("height"))),
+                Winding::Positive,
+            );
+        }
+        "circle" => builder.add_circle(
+            point(number("cx"), number("cy")),
+            number("r"),
+            Winding::Positive,
+        ),
+        "ellipse" => builder.add_ellipse(
+            point(number("cx"), number("cy")),
+            vector(number("rx"), number("ry")),
+            Angle::zero(),
+            Winding::Positive,
+        ),
+        "line" => {
+            builder.begin(point(number("x1"), number("y1")));
+            builder.line_to(point(number("x2"), number("y2")));
+            builder.end(false);
+        }
+        tag @ ("polygon" | "polyline") => {
+            let points: Vec<_> = parse_numbers(node.attribute("points").unwrap_or_default())
+                .chunks_exact(2)
+                .map(|pair| point(pair[0], pair[1]))
+                .collect();
+            builder.add_polygon(Polygon {
+                points: &points,
+                closed: tag == "polygon",
+            });
+        }
+        _ => return Ok(None),
+    }
+
+    Ok(Some(builder.build()))
+}
+
+/// Splits a flattened path into its subpaths and moves them into level space.
+fn outlines(path: &Path, transform: Affine2) -> Vec<Outline> {
+    let mut outlines = Vec::new();
+    let mut points = Vec::new();
+
+    for event in path.iter() {
+        match event {
+            PathEvent::Begin { at } => {
+                points = vec![Vec2::new(at.x, at.y)];
+            }
+            PathEvent::Line { to, .. } => points.push(Vec2::new(to.x, to.y)),
+            PathEvent::End { close, .. } => {
+                // Inkscape often repeats the first point before closing the path.
+                if close && points.len() > 1 && points.first() == points.last() {
+                    points.pop();
+                }
+
+                if points.len() > 1 {
+                    outlines.push(Outline {
+                        points: points
+                            .drain(..)
+                            .map(|point| transform.transform_point2(point))
+                            .collect(),
+                        closed: close,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    outlines
+}
+
+/// The hex colour of `property` (`fill` or `stroke`) without the `#`, looking up
+/// inherited values on ancestors. Only plain `#rgb` and `#rrggbb` colours are supported.
+fn paint(node: Node, property: &str) -> Option<String> {
+    let value = node
+        .ancestors()
+        .filter(Node::is_element)
+        .find_map(|node| style_property(node, property).or_else(|| node.attribute(property)))?;
+
+    let hex = value.trim().strip_prefix('#')?;
+    Color::hex(hex).ok().map(|_| hex.to_string())
+}
+
+/// The value of `property` in the `style` attribute of `node`.
+fn style_property<'a>(node: Node<'a, '_>, property: &str) -> Option<&'a str> {
+    node.attribute("style")?
+        .split(';')
+        .filter_map(|declaration| declaration.split_once(':'))
+        .find(|(name, _)| name.trim() == property)
+        .map(|(_, value)| value.trim())
+}
+
+fn parse_numbers(text: &str) -> Vec<f32> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(|number| number.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(body: &str) -> LevelAsset {
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg"
+                xmlns:inkscape="http://www.inkscape.org/namespaces/inkscape"
+                viewBox="0 0 200 100">{body}</svg>"#
+        );
+        parse_svg_level(&svg, "test").unwrap()
+    }
+
+    #[test]
+    fn walls_are_centred_and_flipped() {
+        let level =
+            level(r#"<rect id="wall-1" x="0" y="0" width="20" height="10" style="fill:#4d4d40"/>"#);
+
+        assert_eq!(level.walls.len(), 1);
+        let wall = &level.walls[0];
+        assert!(!wall.open);
+        assert_eq!(wall.color, "4d4d40");
+        assert_eq!(wall.points.len(), 4);
+        assert!(wall.points.contains(&Vec2::new(-100.0, 50.0)));
+        assert!(wall.points.contains(&Vec2::new(-80.0, 40.0)));
+    }
+
+    #[test]
+    fn roles_are_inherited_from_layers() {
+        let level = level(
+            r#"<g inkscape:groupmode="layer" inkscape:label="Water" transform="translate(100,50)">
+                <path d="M 0,0 H 10 V 10 Z"/>
+            </g>
+            <g class="wall"><path d="M 0,0 L 10,0 L 10,10"/></g>"#,
+        );
+
+        assert_eq!(level.water.len(), 1);
+        assert!(level.water[0].points.contains(&Vec2::new(10.0, -10.0)));
+        assert_eq!(level.walls.len(), 1);
+        assert!(level.walls[0].open);
+        assert_eq!(level.walls[0].points.len(), 3);
+    }
+
+    #[test]
+    fn open_spawn_paths_face_their_end() {
+        let level = level(
+            r#"<path id="spawn-blue" d="M 100,50 L 100,0"/>
+            <circle class="spawn-red" cx="50" cy="50" r="5"/>"#,
+        );
+
+        let blue = level
+            .spawns
+            .iter()
+            .find(|spawn| spawn.team == Team::Blue)
+            .unwrap();
+        assert_eq!(blue.position, Vec2::ZERO);
+        assert!((blue.rotation - 90.0).abs() < 1e-3);
+
+        let red = level
+            .spawns
+            .iter()
+            .find(|spawn| spawn.team == Team::Red)
+            .unwrap();
+        assert!(red.position.distance(Vec2::new(-50.0, 0.0)) < 1.0);
+    }
+
+    #[test]
+    fn hidden_and_unfilled_shapes_are_skipped() {
+        let level = level(
+            r##"<defs><rect id="wall" width="10" height="10"/></defs>
+            <g style="display:none"><rect class="wall" width="10" height="10"/></g>
+            <rect width="10" height="10" style="fill:none;stroke:#000000"/>
+            <rect width="10" height="10" fill="#336699"/>"##,
+        );
+
+        assert!(level.walls.is_empty());
+        assert_eq!(level.props.len(), 1);
+        assert_eq!(level.props[0].color, "336699");
+    }
+
+    #[test]
+    fn destructible_class_marks_closed_walls() {
+        let level = level(
+            r#"<g id="walls">
+                <rect class="destructible" width="10" height="10"/>
+                <path class="destructible" d="M 0,0 L 10,0"/>
+            </g>"#,
+        );
+
+        assert!(level.walls[0].destructible.is_some());
+        assert!(level.walls[1].destructible.is_none());
+    }
+}
@@ -0,0 +1,264 @@
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::components::*;
+use super::definition::*;
+use super::events::*;
+use super::generator::*;
+use crate::combat::components::Health;
+use crate::tank::components::Tank;
+use crate::tracks::components::Tracks;
+use crate::GameSessionEntity;
+
+const PICKUP_RADIUS: f32 = 15.0;
+const FENCE_WIDTH: f32 = 4.0;
+/// Share of their top speed tanks keep while driving through water.
+const WATER_SPEED_FACTOR: f32 = 0.4;
+
+pub fn load_levels(mut library: ResMut<LevelLibrary>, asset_server: Res<AssetServer>) {
+    library.levels = match asset_server.load_folder("levels") {
+        Ok(handles) => handles
+            .into_iter()
+            .map(|handle| handle.typed::<LevelAsset>())
+            .collect(),
+        Err(error) => {
+            warn!("Could not load levels: {error:?}");
+            Vec::new()
+        }
+    };
+}
+
+pub fn request_selected_level(
+    mut commands: Commands,
+    selected_level: Res<SelectedLevel>,
+    seed: Res<LevelSeed>,
+    mut library: ResMut<LevelLibrary>,
+    mut levels: ResMut<Assets<LevelAsset>>,
+) {
+    if selected_level.0 == PROCEDURAL_LEVEL_NAME {
+        library.generated = Some(levels.add(generate_level(seed.0)));
+    }
+
+    commands.spawn((
+        Name::new(format!("Level {}", selected_level.0)),
+        GameSessionEntity,
+        LevelSpawnRequest {
+            name: selected_level.0.clone(),
+        },
+    ));
+}
+
+pub fn spawn_requested_levels(
+    mut commands: Commands,
+    mut spawned_events: EventWriter<LevelSpawned>,
+    library: Res<LevelLibrary>,
+    levels: Res<Assets<LevelAsset>>,
+    request_query: Query<(Entity, &LevelSpawnRequest)>,
+) {
+    for (entity, request) in request_query.iter() {
+        if let Some(level) = library.get(&levels, &request.name) {
+            let mut root = commands.entity(entity);
+            root.remove::<LevelSpawnRequest>();
+            spawn_level(&mut root, level);
+
+            spawned_events.send(LevelSpawned {
+                spawns: level.spawns.clone(),
+            });
+        }
+    }
+}
+
+/// Builds the walls, water, props and pickups of `level` as children of the entity behind `commands`.
+fn spawn_level(commands: &mut EntityCommands, level: &LevelAsset) {
+    commands.insert((
+        Level,
+        SpatialBundle::default(),
+        Name::new(level.name.clone()),
+    ));
+
+    commands.with_children(|parent| {
+        for wall in level.walls.iter() {
+            if wall.open {
+                parent.spawn((
+                    polyline_shape(&wall.points, 0.0),
+                    Stroke::new(wall.color(), FENCE_WIDTH),
+                    Name::new("Fence"),
+                    Wall,
+                    Collider::polyline(wall.points.clone(), None),
+                ));
+                continue;
+            }
+
+            let (points, center) = centred(&wall.points);
+            let mut entity = parent.spawn((
+                polygon_shape(&points, center.extend(0.0)),
+                Fill::color(wall.color()),
+                Stroke::new(Color::hex("191919").unwrap(), 2.0),
+                Name::new("Wall"),
+                Wall,
+            ));
+
+            match polygon_collider(&points) {
+                Some(collider) => {
+                    entity.insert(collider);
+                }
+                None => warn!("Level {} has a wall with fewer than 3 points", level.name),
+            }
+
+            if let Some(destructible) = wall.destructible {
+                entity.insert((destructible, Health::new(destructible.health)));
+            }
+        }
+
+        for water in level.water.iter() {
+            let (points, center) = centred(&water.points);
+            let mut entity = parent.spawn((
+                polygon_shape(&points, center.extend(-0.5)),
+                Fill::color(Color::hex("2f5f8f").unwrap()),
+                Name::new("Water"),
+                Water,
+            ));
+
+            match polygon_collider(&points) {
+                Some(collider) => {
+                    entity.insert((collider, Sensor));
+                }
+                None => warn!("Level {} has water with fewer than 3 points", level.name),
+            }
+        }
+
+        for prop in level.props.iter() {
+            let (points, center) = centred(&prop.points);
+            let mut entity = parent.spawn((
+                polygon_shape(&points, center.extend(prop.z)),
+                Fill::color(prop.color()),
+                Name::new("Prop"),
+                Prop,
+            ));
+
+            // Only props that can be shot at need a collider.
+            if let Some(destructible) = prop.destructible {
+                match polygon_collider(&points) {
+                    Some(collider) => {
+                        entity.insert((collider, destructible, Health::new(destructible.health)));
+                    }
+                    None => warn!("Level {} has a prop with fewer than 3 points", level.name),
+                }
+            }
+        }
+
+        for pickup in level.pickups.iter() {
+            parent.spawn((
+                ShapeBundle {
+                    path: GeometryBuilder::new()
+                        .add(&shapes::Circle {
+                            radius: PICKUP_RADIUS,
+                            ..default()
+                        })
+                        .build(),
+                    transform: Transform::from_translation(pickup.position.extend(0.05)),
+                    ..default()
+                },
+                Fill::color(Color::hex("30bf30").unwrap()),
+                Stroke::new(Color::hex("191919").unwrap(), 2.0),
+                Name::new("Pickup"),
+                Pickup { kind: pickup.kind },
+                Collider::ball(PICKUP_RADIUS),
+                Sensor,
+                ActiveEvents::COLLISION_EVENTS,
+            ));
+        }
+    });
+}
+
+/// Moves `points` around their centre, so that entities sit where their shape is.
+fn centred(points: &[Vec2]) -> (Vec<Vec2>, Vec2) {
+    let min = points.iter().copied().reduce(Vec2::min).unwrap_or_default();
+    let max = points.iter().copied().reduce(Vec2::max).unwrap_or_default();
+    let center = (min + max) / 2.0;
+
+    (points.iter().map(|point| *point - center).collect(), center)
+}
+
+fn polygon_shape(points: &[Vec2], translation: Vec3) -> ShapeBundle {
+    ShapeBundle {
+        path: GeometryBuilder::new()
+            .add(&shapes::Polygon {
+                points: points.to_vec(),
+                closed: true,
+            })
+            .build(),
+        transform: Transform::from_translation(translation),
+        ..default()
+    }
+}
+
+fn polyline_shape(points: &[Vec2], z: f32) -> ShapeBundle {
+    ShapeBundle {
+        path: GeometryBuilder::new()
+            .add(&shapes::Polygon {
+                points: points.to_vec(),
+                closed: false,
+            })
+            .build(),
+        transform: Transform::from_xyz(0.0, 0.0, z),
+        ..default()
+    }
+}
+
+/// Concave polygons are split into convex parts so their inside is solid too.
+fn polygon_collider(points: &[Vec2]) -> Option<Collider> {
+    if points.len() < 3 {
+        return None;
+    }
+
+    let count = points.len() as u32;
+    let indices: Vec<[u32; 2]> = (0..count).map(|i| [i, (i + 1) % count]).collect();
+
+    Some(Collider::convex_decomposition(points, &indices))
+}
+
+pub fn collect_pickups(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    pickup_query: Query<&Pickup>,
+    mut tank_query: Query<&mut Health, With<Tank>>,
+) {
+    for collision_event in collision_events.iter() {
+        if let CollisionEvent::Started(entity1, entity2, _) = *collision_event {
+            for (pickup_entity, collector) in [(entity1, entity2), (entity2, entity1)] {
+                let (Ok(pickup), Ok(mut health)) = (
+                    pickup_query.get(pickup_entity),
+                    tank_query.get_mut(collector),
+                ) else {
+                    continue;
+                };
+
+                match pickup.kind {
+                    PickupKind::Repair(amount) => {
+                        health.current = (health.current + amount).min(health.max);
+                    }
+                }
+
+                commands.entity(pickup_entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+pub fn wade_through_water(
+    rapier_context: Res<RapierContext>,
+    water_query: Query<(), With<Water>>,
+    mut tracks_query: Query<(Entity, &mut Tracks)>,
+) {
+    for (entity, mut tracks) in tracks_query.iter_mut() {
+        let in_water = rapier_context.intersections_with(entity).any(
+            |(collider1, collider2, intersecting)| {
+                intersecting && (water_query.contains(collider1) || water_query.contains(collider2))
+            },
+        );
+
+        tracks.speed_factor = if in_water { WATER_SPEED_FACTOR } else { 1.0 };
+    }
+}
@@ -1,26 +1,85 @@
+mod ai;
+mod combat;
+mod controls_menu;
+mod editor;
 pub mod first;
+mod gun;
+mod hud;
+mod level;
 mod main_menu;
+mod menu;
+mod navigation;
+mod pause_menu;
 mod player;
+mod settings;
+mod settings_menu;
+mod spotting;
 mod systems;
+mod tank;
+mod tracks;
+mod ui_focus;
 
+use ai::AiPlugin;
 use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
 use bevy::prelude::*;
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 use bevy_prototype_debug_lines::*;
+use combat::CombatPlugin;
+use controls_menu::ControlsMenuPlugin;
+use editor::EditorPlugin;
 use first::GamePlugin;
+use gun::GunPlugin;
+use hud::HudPlugin;
+use input_map::InputMapPlugin;
+use level::LevelPlugin;
 use main_menu::MainMenuPlugin;
+use menu::MenuPlugin;
+use navigation::NavigationPlugin;
 use pancam::*;
+use pause_menu::PauseMenuPlugin;
 use physics_2d_plugin::*;
 use player::PlayerPlugin;
+use settings::SettingsPlugin;
+use settings_menu::SettingsMenuPlugin;
+use spotting::SpottingPlugin;
 use systems::*;
+use tank::TankPlugin;
+use tracks::TracksPlugin;
+use ui_focus::UiFocusPlugin;
 
 #[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
 pub enum AppState {
     #[default]
     MainMenu,
     Game,
+    Editor,
+    Controls,
 }
 
+/// Whether the game world is advancing. Only meaningful in `AppState::Game`.
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
+pub enum SimulationState {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// Whether the settings screen is open, in place of the main or pause menu.
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
+pub enum SettingsState {
+    #[default]
+    Closed,
+    Open,
+}
+
+/// Systems that advance the game world and stop while it is paused.
+#[derive(SystemSet, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct GameplaySet;
+
+/// Everything that belongs to the current game session and is despawned when it ends.
+#[derive(Component)]
+pub struct GameSessionEntity;
+
 #[derive(Component)]
 pub struct MainCamera;
 
@@ -46,17 +105,60 @@ fn main() {
         .add_plugin(PhysicsPlugin)
         .add_plugin(PanCamPlugin::default())
         .add_plugin(WorldInspectorPlugin::default())
-        .insert_resource(Msaa::Sample4)
+        .add_plugin(SettingsPlugin)
+        .add_plugin(InputMapPlugin)
+        .add_plugin(UiFocusPlugin)
         // OnEnter State Systems
         .add_state::<AppState>()
+        .add_state::<SimulationState>()
+        .add_state::<SettingsState>()
+        // Members of the set live in several base sets, so it is gated by run conditions
+        // instead of being nested in `OnUpdate`.
+        .configure_set(
+            GameplaySet
+                .run_if(in_state(AppState::Game))
+                .run_if(in_state(SimulationState::Running)),
+        )
+        .add_plugin(MenuPlugin)
         .add_plugin(MainMenuPlugin)
+        .add_plugin(PauseMenuPlugin)
+        .add_plugin(ControlsMenuPlugin)
+        .add_plugin(SettingsMenuPlugin)
+        .add_plugin(HudPlugin)
+        .add_plugin(EditorPlugin)
         .add_plugin(GamePlugin)
         .add_plugin(PlayerPlugin)
+        .add_plugin(GunPlugin)
+        .add_plugin(CombatPlugin)
+        .add_plugin(TracksPlugin)
+        .add_plugin(TankPlugin)
+        .add_plugin(LevelPlugin)
+        .add_plugin(NavigationPlugin)
+        .add_plugin(SpottingPlugin)
+        .add_plugin(AiPlugin)
         .add_startup_system(setup_camera)
         // .add_system(my_cursor_system)
         // .add_system(my_print_cursor_system)
-        .add_system(transition_to_game_state)
-        .add_system(transition_to_main_menu_state)
-        .add_system(bevy::window::close_on_esc)
+        // Keys pressed on the controls screen are meant for rebinding, and the settings
+        // screen is left with its back button.
+        .add_system(
+            transition_to_game_state
+                .run_if(not(in_state(AppState::Controls)))
+                .run_if(in_state(SettingsState::Closed)),
+        )
+        .add_system(
+            transition_to_main_menu_state
+                .run_if(not(in_state(AppState::Controls)))
+                .run_if(in_state(SettingsState::Closed)),
+        )
+        .add_system(
+            toggle_pause
+                .in_set(OnUpdate(AppState::Game))
+                .run_if(in_state(SettingsState::Closed)),
+        )
+        .add_system(pause_physics.in_schedule(OnEnter(SimulationState::Paused)))
+        .add_system(resume_physics.in_schedule(OnExit(SimulationState::Paused)))
+        .add_system(resume_simulation.in_schedule(OnExit(AppState::Game)))
+        .add_system(despawn_game_session.in_schedule(OnExit(AppState::Game)))
         .run();
 }
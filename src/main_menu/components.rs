@@ -1,10 +1,12 @@
-use bevy::prelude::Component;
+use bevy::prelude::{Component, Resource};
 
 #[derive(Component)]
 pub struct MainMenu {}
 
-#[derive(Component)]
-pub struct PlayButton {}
-
-#[derive(Component)]
-pub struct QuitButton {}
+/// Set by the seed button: while editing, typed digits collect in `digits` and become
+/// the level seed when the button is pressed again.
+#[derive(Resource, Default)]
+pub struct SeedInput {
+    pub editing: bool,
+    pub digits: String,
+}
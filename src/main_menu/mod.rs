@@ -1,24 +1,41 @@
 mod components;
-mod styles;
 mod systems;
 
+use components::SeedInput;
 use systems::interactions::*;
 use systems::layout::*;
 
 use bevy::prelude::*;
 
-use crate::AppState;
+use crate::menu::systems::interact_with_menu_buttons;
+use crate::{AppState, SettingsState};
 
 pub struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
-        app
+        app.init_resource::<SeedInput>()
             // OnEnter State Systems
             .add_system(spawn_main_menu.in_schedule(OnEnter(AppState::MainMenu)))
+            // The settings screen takes the place of the menu while it is open.
+            .add_system(despawn_main_menu.in_schedule(OnEnter(SettingsState::Open)))
+            .add_system(
+                spawn_main_menu
+                    .in_schedule(OnExit(SettingsState::Open))
+                    .run_if(in_state(AppState::MainMenu)),
+            )
             // Systems
             .add_systems(
-                (interact_with_play_button, interact_with_quit_button)
+                (
+                    handle_main_menu_buttons,
+                    handle_loadout_buttons,
+                    handle_level_buttons,
+                )
+                    .after(interact_with_menu_buttons)
+                    .in_set(OnUpdate(AppState::MainMenu)),
+            )
+            .add_systems(
+                (type_seed, update_loadout_labels, update_level_labels)
                     .in_set(OnUpdate(AppState::MainMenu)),
             )
             // OnExit State Systems
@@ -1,48 +1,164 @@
 use bevy::app::AppExit;
 use bevy::prelude::*;
 
+use crate::level::{
+    components::{LevelLibrary, LevelSeed, SelectedLevel},
+    definition::LevelAsset,
+};
 use crate::main_menu::components::*;
-use crate::main_menu::styles::{HOVERED_BUTTON_COLOR, NORMAL_BUTTON_COLOR, PRESSED_BUTTON_COLOR};
-use crate::AppState;
+use crate::menu::{
+    components::{MenuAction, MenuButton},
+    events::MenuButtonPressed,
+    systems::set_button_label,
+};
+use crate::tank::{
+    components::{Garage, Loadout},
+    definition::{HullDefinition, TurretDefinition},
+};
+use crate::{AppState, SettingsState};
 
-type PlayButtonInteraction<'a> = (&'a Interaction, &'a mut BackgroundColor);
-
-pub fn interact_with_play_button(
-    mut button_query: Query<PlayButtonInteraction, (Changed<Interaction>, With<PlayButton>)>,
+pub fn handle_main_menu_buttons(
+    mut pressed_events: EventReader<MenuButtonPressed>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
+    mut settings_state_next_state: ResMut<NextState<SettingsState>>,
+    mut app_exit_event_writer: EventWriter<AppExit>,
 ) {
-    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
-        match *interaction {
-            Interaction::Clicked => {
-                *background_color = PRESSED_BUTTON_COLOR.into();
-                app_state_next_state.set(AppState::Game);
-            }
-            Interaction::Hovered => {
-                *background_color = HOVERED_BUTTON_COLOR.into();
+    for pressed in pressed_events.iter() {
+        match pressed.action {
+            MenuAction::Play => app_state_next_state.set(AppState::Game),
+            MenuAction::Controls => app_state_next_state.set(AppState::Controls),
+            MenuAction::Editor => app_state_next_state.set(AppState::Editor),
+            MenuAction::Settings => settings_state_next_state.set(SettingsState::Open),
+            MenuAction::Quit => app_exit_event_writer.send(AppExit),
+            _ => {}
+        }
+    }
+}
+
+pub fn handle_loadout_buttons(
+    mut pressed_events: EventReader<MenuButtonPressed>,
+    mut loadout: ResMut<Loadout>,
+    garage: Res<Garage>,
+    asset_server: Res<AssetServer>,
+) {
+    for pressed in pressed_events.iter() {
+        match pressed.action {
+            MenuAction::NextHull => {
+                if let Some(hull) = garage.next_hull(&loadout.hull, &asset_server) {
+                    loadout.hull = hull;
+                }
             }
-            Interaction::None => {
-                *background_color = NORMAL_BUTTON_COLOR.into();
+            MenuAction::NextTurret => {
+                if let Some(turret) = garage.next_turret(&loadout.turret, &asset_server) {
+                    loadout.turret = turret;
+                }
             }
+            _ => {}
         }
     }
 }
 
-pub fn interact_with_quit_button(
-    mut app_exit_event_writer: EventWriter<AppExit>,
-    mut button_query: Query<PlayButtonInteraction, (Changed<Interaction>, With<QuitButton>)>,
+pub fn handle_level_buttons(
+    mut pressed_events: EventReader<MenuButtonPressed>,
+    mut selected_level: ResMut<SelectedLevel>,
+    mut seed_input: ResMut<SeedInput>,
+    mut seed: ResMut<LevelSeed>,
+    library: Res<LevelLibrary>,
+    levels: Res<Assets<LevelAsset>>,
 ) {
-    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
-        match *interaction {
-            Interaction::Clicked => {
-                *background_color = PRESSED_BUTTON_COLOR.into();
-                app_exit_event_writer.send(AppExit);
+    for pressed in pressed_events.iter() {
+        match pressed.action {
+            MenuAction::NextLevel => {
+                if let Some(level) = library.next_name(&levels, &selected_level.0) {
+                    selected_level.0 = level;
+                }
             }
-            Interaction::Hovered => {
-                *background_color = HOVERED_BUTTON_COLOR.into();
+            MenuAction::EditSeed if seed_input.editing => {
+                // Stopping without typing anything keeps the old seed.
+                if let Ok(typed) = seed_input.digits.parse() {
+                    seed.0 = typed;
+                }
+                seed_input.editing = false;
             }
-            Interaction::None => {
-                *background_color = NORMAL_BUTTON_COLOR.into();
+            MenuAction::EditSeed => {
+                seed_input.digits.clear();
+                seed_input.editing = true;
             }
+            _ => {}
+        }
+    }
+}
+
+/// Typed digits are collected while the seed button is being edited, as long as they
+/// still make a valid seed.
+pub fn type_seed(
+    mut received_characters: EventReader<ReceivedCharacter>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut seed_input: ResMut<SeedInput>,
+) {
+    if !seed_input.editing {
+        received_characters.clear();
+        return;
+    }
+
+    for digit in received_characters
+        .iter()
+        .map(|received| received.char)
+        .filter(char::is_ascii_digit)
+    {
+        let typed = format!("{}{digit}", seed_input.digits);
+        if typed.parse::<u64>().is_ok() {
+            seed_input.digits = typed;
         }
     }
+
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        seed_input.digits.pop();
+    }
+}
+
+/// Shows the names of the selected parts on the garage buttons.
+pub fn update_loadout_labels(
+    loadout: Res<Loadout>,
+    asset_server: Res<AssetServer>,
+    hulls: Res<Assets<HullDefinition>>,
+    turrets: Res<Assets<TurretDefinition>>,
+    button_query: Query<(&Children, &MenuButton)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let hull = hulls
+        .get(&asset_server.load(loadout.hull.as_str()))
+        .map_or("...", |hull| hull.name.as_str());
+    let turret = turrets
+        .get(&asset_server.load(loadout.turret.as_str()))
+        .map_or("...", |turret| turret.name.as_str());
+
+    for (children, menu_button) in button_query.iter() {
+        let label = match menu_button.action {
+            MenuAction::NextHull => format!("Hull: {hull}"),
+            MenuAction::NextTurret => format!("Turret: {turret}"),
+            _ => continue,
+        };
+        set_button_label(children, &mut text_query, &label);
+    }
+}
+
+pub fn update_level_labels(
+    selected_level: Res<SelectedLevel>,
+    seed: Res<LevelSeed>,
+    seed_input: Res<SeedInput>,
+    button_query: Query<(&Children, &MenuButton)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (children, menu_button) in button_query.iter() {
+        let label = match menu_button.action {
+            MenuAction::NextLevel => format!("Level: {}", selected_level.0),
+            MenuAction::EditSeed if seed_input.editing => {
+                format!("Seed: {}_", seed_input.digits)
+            }
+            MenuAction::EditSeed => format!("Seed: {}", seed.0),
+            _ => continue,
+        };
+        set_button_label(children, &mut text_query, &label);
+    }
 }
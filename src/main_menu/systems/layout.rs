@@ -1,108 +1,44 @@
 use bevy::prelude::*;
 
 use crate::main_menu::components::*;
-use crate::main_menu::styles::*;
+use crate::menu::components::MenuAction;
+use crate::menu::screen::{MenuItem, MenuScreen};
 
 pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
     build_main_menu(&mut commands, &asset_server);
 }
 
-pub fn despawn_main_menu(mut commands: Commands, main_menu_query: Query<Entity, With<MainMenu>>) {
+pub fn despawn_main_menu(
+    mut commands: Commands,
+    main_menu_query: Query<Entity, With<MainMenu>>,
+    mut seed_input: ResMut<SeedInput>,
+) {
     if let Ok(main_menu_entity) = main_menu_query.get_single() {
         commands.entity(main_menu_entity).despawn_recursive();
     }
+    // Leaving the menu while typing keeps the old seed.
+    seed_input.editing = false;
 }
 
 pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
-    let main_menu_entity = commands
-        .spawn((
-            NodeBundle {
-                style: MAIN_MENU_STYLE,
-                ..default()
-            },
-            MainMenu {},
-        ))
-        .with_children(|parent| {
-            // === Title ===
-            parent
-                .spawn(NodeBundle {
-                    style: TITLE_STYLE,
-                    ..default()
-                })
-                .with_children(|parent| {
-                    // Image 1
-                    parent.spawn(ImageBundle {
-                        style: IMAGE_STYLE,
-                        image: asset_server.load("sprites/ball_blue_large.png").into(),
-                        ..default()
-                    });
-                    // Text
-                    parent.spawn(TextBundle {
-                        text: Text {
-                            sections: vec![TextSection::new(
-                                "Bevy Ball Game",
-                                get_title_text_style(asset_server),
-                            )],
-                            alignment: TextAlignment::Center,
-                            ..default()
-                        },
-                        ..default()
-                    });
-                    // Image 2
-                    parent.spawn(ImageBundle {
-                        style: IMAGE_STYLE,
-                        image: asset_server.load("sprites/ball_red_large.png").into(),
-                        ..default()
-                    });
-                });
-            // === Play Button ===
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: BUTTON_STYLE,
-                        background_color: NORMAL_BUTTON_COLOR.into(),
-                        ..default()
-                    },
-                    PlayButton {},
-                ))
-                .with_children(|parent| {
-                    parent.spawn(TextBundle {
-                        text: Text {
-                            sections: vec![TextSection::new(
-                                "Play",
-                                get_button_text_style(asset_server),
-                            )],
-                            alignment: TextAlignment::Center,
-                            ..default()
-                        },
-                        ..default()
-                    });
-                });
-            // === Quit Button ===
-            parent
-                .spawn((
-                    ButtonBundle {
-                        style: BUTTON_STYLE,
-                        background_color: NORMAL_BUTTON_COLOR.into(),
-                        ..default()
-                    },
-                    QuitButton {},
-                ))
-                .with_children(|parent| {
-                    parent.spawn(TextBundle {
-                        text: Text {
-                            sections: vec![TextSection::new(
-                                "Quit",
-                                get_button_text_style(asset_server),
-                            )],
-                            alignment: TextAlignment::Center,
-                            ..default()
-                        },
-                        ..default()
-                    });
-                });
-        })
-        .id();
-
-    main_menu_entity
+    MenuScreen {
+        title: "Bevy Ball Game",
+        title_images: Some(["sprites/ball_blue_large.png", "sprites/ball_red_large.png"]),
+        items: vec![
+            MenuItem::button("Play", MenuAction::Play),
+            // The labels are filled in with the current selection.
+            MenuItem::wide_button("Hull", MenuAction::NextHull),
+            MenuItem::wide_button("Turret", MenuAction::NextTurret),
+            MenuItem::wide_button("Level", MenuAction::NextLevel),
+            MenuItem::wide_button("Seed", MenuAction::EditSeed),
+            MenuItem::row(vec![
+                MenuItem::button("Controls", MenuAction::Controls),
+                MenuItem::button("Settings", MenuAction::Settings),
+                MenuItem::button("Editor", MenuAction::Editor),
+                MenuItem::button("Quit", MenuAction::Quit),
+            ]),
+        ],
+        ..default()
+    }
+    .spawn(commands, asset_server, MainMenu {})
 }
@@ -0,0 +1,35 @@
+use bevy::prelude::Component;
+use input_map::actions::Action;
+
+/// A button of any menu. Pressing it sends `MenuButtonPressed` with its `action`.
+#[derive(Component)]
+pub struct MenuButton {
+    pub action: MenuAction,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuAction {
+    Play,
+    NextHull,
+    NextTurret,
+    NextLevel,
+    /// Start or stop typing a new level seed.
+    EditSeed,
+    Controls,
+    Editor,
+    Quit,
+    Resume,
+    Settings,
+    QuitToMenu,
+    /// Bind the next key or button pressed to a slot of the action.
+    Rebind(Action, usize),
+    ResetBindings,
+    NextMsaa,
+    NextFrameLimit,
+    NextWindowMode,
+    NextVolume,
+    ToggleZoomToCursor,
+    NextMaxZoomOut,
+    ResetSettings,
+    Back,
+}
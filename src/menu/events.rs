@@ -0,0 +1,7 @@
+use super::components::MenuAction;
+
+/// A `MenuButton` was clicked, or pressed with the keyboard or a gamepad.
+#[derive(Debug, Clone, Copy)]
+pub struct MenuButtonPressed {
+    pub action: MenuAction,
+}
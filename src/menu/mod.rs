@@ -0,0 +1,20 @@
+pub mod components;
+pub mod events;
+pub mod screen;
+pub mod styles;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use events::*;
+use systems::*;
+
+/// Shared buttons and layout for the main, pause and controls menus.
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<MenuButtonPressed>()
+            .add_system(interact_with_menu_buttons);
+    }
+}
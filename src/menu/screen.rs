@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+
+use super::components::*;
+use super::styles::*;
+
+/// A menu described as data: a title over a column of buttons, labels and rows of them.
+pub struct MenuScreen {
+    pub title: &'static str,
+    /// Shown left and right of the title.
+    pub title_images: Option<[&'static str; 2]>,
+    pub style: Style,
+    pub background_color: Color,
+    pub items: Vec<MenuItem>,
+}
+
+impl Default for MenuScreen {
+    fn default() -> Self {
+        Self {
+            title: "",
+            title_images: None,
+            style: MENU_STYLE,
+            background_color: Color::NONE,
+            items: Vec::new(),
+        }
+    }
+}
+
+pub enum MenuItem {
+    Button {
+        label: String,
+        action: MenuAction,
+        style: Style,
+        font_size: f32,
+    },
+    Label {
+        text: String,
+        style: Style,
+        font_size: f32,
+    },
+    /// Items side by side.
+    Row { style: Style, items: Vec<MenuItem> },
+}
+
+impl MenuItem {
+    pub fn button(label: &str, action: MenuAction) -> Self {
+        MenuItem::Button {
+            label: label.to_string(),
+            action,
+            style: BUTTON_STYLE,
+            font_size: BUTTON_FONT_SIZE,
+        }
+    }
+
+    /// Leaves room for labels that change, like the name of the selected hull.
+    pub fn wide_button(label: &str, action: MenuAction) -> Self {
+        MenuItem::Button {
+            label: label.to_string(),
+            action,
+            style: WIDE_BUTTON_STYLE,
+            font_size: BUTTON_FONT_SIZE,
+        }
+    }
+
+    pub fn row(items: Vec<MenuItem>) -> Self {
+        MenuItem::Row {
+            style: MENU_ROW_STYLE,
+            items,
+        }
+    }
+}
+
+impl MenuScreen {
+    /// Spawns the menu under a root node with `marker`, so despawning that removes it all.
+    pub fn spawn(
+        &self,
+        commands: &mut Commands,
+        asset_server: &Res<AssetServer>,
+        marker: impl Bundle,
+    ) -> Entity {
+        commands
+            .spawn((
+                NodeBundle {
+                    style: self.style.clone(),
+                    background_color: self.background_color.into(),
+                    ..default()
+                },
+                marker,
+            ))
+            .with_children(|parent| {
+                // === Title ===
+                parent
+                    .spawn(NodeBundle {
+                        style: TITLE_STYLE,
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        if let Some([left_image, _]) = self.title_images {
+                            spawn_image(parent, asset_server, left_image);
+                        }
+                        parent.spawn(TextBundle {
+                            text: Text {
+                                sections: vec![TextSection::new(
+                                    self.title,
+                                    get_title_text_style(asset_server),
+                                )],
+                                alignment: TextAlignment::Center,
+                                ..default()
+                            },
+                            ..default()
+                        });
+                        if let Some([_, right_image]) = self.title_images {
+                            spawn_image(parent, asset_server, right_image);
+                        }
+                    });
+
+                for item in self.items.iter() {
+                    spawn_item(parent, asset_server, item);
+                }
+            })
+            .id()
+    }
+}
+
+fn spawn_image(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, path: &str) {
+    parent.spawn(ImageBundle {
+        style: IMAGE_STYLE,
+        image: asset_server.load(path).into(),
+        ..default()
+    });
+}
+
+fn spawn_item(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, item: &MenuItem) {
+    match item {
+        MenuItem::Button {
+            label,
+            action,
+            style,
+            font_size,
+        } => {
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: style.clone(),
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    MenuButton { action: *action },
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text {
+                            sections: vec![TextSection::new(
+                                label.as_str(),
+                                get_text_style(asset_server, *font_size),
+                            )],
+                            alignment: TextAlignment::Center,
+                            ..default()
+                        },
+                        ..default()
+                    });
+                });
+        }
+        MenuItem::Label {
+            text,
+            style,
+            font_size,
+        } => {
+            parent.spawn(
+                TextBundle::from_section(text.as_str(), get_text_style(asset_server, *font_size))
+                    .with_style(style.clone()),
+            );
+        }
+        MenuItem::Row { style, items } => {
+            parent
+                .spawn(NodeBundle {
+                    style: style.clone(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for item in items.iter() {
+                        spawn_item(parent, asset_server, item);
+                    }
+                });
+        }
+    }
+}
@@ -3,8 +3,12 @@ use bevy::prelude::*;
 pub const NORMAL_BUTTON_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
 pub const HOVERED_BUTTON_COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
 pub const PRESSED_BUTTON_COLOR: Color = Color::rgb(0.35, 0.75, 0.35);
+/// The button Enter or the gamepad would press.
+pub const FOCUSED_BUTTON_COLOR: Color = Color::rgb(0.2, 0.3, 0.5);
 
-pub const MAIN_MENU_STYLE: Style = Style {
+pub const BUTTON_FONT_SIZE: f32 = 32.0;
+
+pub const MENU_STYLE: Style = Style {
     flex_direction: FlexDirection::Column,
     justify_content: JustifyContent::Center,
     align_items: AlignItems::Center,
@@ -13,6 +17,13 @@ pub const MAIN_MENU_STYLE: Style = Style {
     ..Style::DEFAULT
 };
 
+pub const MENU_ROW_STYLE: Style = Style {
+    flex_direction: FlexDirection::Row,
+    align_items: AlignItems::Center,
+    gap: Size::new(Val::Px(8.0), Val::Px(8.0)),
+    ..Style::DEFAULT
+};
+
 pub const BUTTON_STYLE: Style = Style {
     justify_content: JustifyContent::Center,
     align_items: AlignItems::Center,
@@ -20,6 +31,13 @@ pub const BUTTON_STYLE: Style = Style {
     ..Style::DEFAULT
 };
 
+pub const WIDE_BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(400.0), Val::Px(80.0)),
+    ..Style::DEFAULT
+};
+
 pub const IMAGE_STYLE: Style = Style {
     size: Size::new(Val::Px(64.0), Val::Px(64.0)),
     margin: UiRect::new(Val::Px(8.0), Val::Px(8.0), Val::Px(8.0), Val::Px(8.0)),
@@ -42,10 +60,10 @@ pub fn get_title_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
     }
 }
 
-pub fn get_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+pub fn get_text_style(asset_server: &Res<AssetServer>, font_size: f32) -> TextStyle {
     TextStyle {
         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-        font_size: 32.0,
+        font_size,
         color: Color::WHITE,
     }
 }
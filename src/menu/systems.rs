@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+
+use super::components::*;
+use super::events::*;
+use super::styles::{HOVERED_BUTTON_COLOR, NORMAL_BUTTON_COLOR, PRESSED_BUTTON_COLOR};
+
+type MenuButtonInteraction<'a> = (&'a Interaction, &'a mut BackgroundColor, &'a MenuButton);
+
+pub fn interact_with_menu_buttons(
+    mut button_query: Query<MenuButtonInteraction, Changed<Interaction>>,
+    mut pressed_events: EventWriter<MenuButtonPressed>,
+) {
+    for (interaction, mut background_color, menu_button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                pressed_events.send(MenuButtonPressed {
+                    action: menu_button.action,
+                });
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+/// Sets the text of a button spawned by `MenuScreen`.
+pub fn set_button_label(children: &Children, text_query: &mut Query<&mut Text>, label: &str) {
+    for child in children.iter() {
+        if let Ok(mut text) = text_query.get_mut(*child) {
+            if text.sections[0].value != label {
+                text.sections[0].value = label.to_string();
+            }
+        }
+    }
+}
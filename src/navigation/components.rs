@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use super::flow_field::FlowField;
+
+/// Steers its rigid body along the shared `SwarmFlowField`.
+#[derive(Copy, Clone, Debug, PartialEq, Component)]
+pub struct FlowFieldAgent {
+    pub speed: f32,
+}
+
+/// The entity the swarm converges on.
+#[derive(Component)]
+pub struct FlowFieldGoal;
+
+/// The flow field towards the `FlowFieldGoal`, shared by every `FlowFieldAgent`.
+#[derive(Resource, Debug, Default)]
+pub struct SwarmFlowField {
+    pub field: Option<FlowField>,
+    /// Goal position the field was last built for.
+    pub built_for: Option<Vec2>,
+}
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct FlowFieldSettings {
+    /// Clearance agents keep from static obstacles.
+    pub agent_radius: f32,
+    /// How far the goal may move before the field is rebuilt. The field is
+    /// also rebuilt whenever the `NavGrid` changes.
+    pub rebuild_distance: f32,
+    /// Draw the field around the goal with `DebugLines`.
+    pub show_arrows: bool,
+}
+
+impl Default for FlowFieldSettings {
+    fn default() -> Self {
+        Self {
+            agent_radius: 10.0,
+            rebuild_distance: 40.0,
+            show_arrows: false,
+        }
+    }
+}
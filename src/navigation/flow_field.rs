@@ -0,0 +1,171 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use bevy::prelude::*;
+
+use super::grid::NavGrid;
+
+/// Directions towards one goal for every cell of a `NavGrid`, shared by any
+/// number of agents of the same radius.
+#[derive(Debug, Clone)]
+pub struct FlowField {
+    size: UVec2,
+    /// Cost of the cheapest route from each cell to the goal, in cells.
+    integration: Vec<f32>,
+    directions: Vec<Vec2>,
+}
+
+impl FlowField {
+    /// Integrates outwards from the cell containing `goal`. `None` if the goal
+    /// is outside the grid or not walkable for `agent_radius`.
+    pub fn build(nav_grid: &NavGrid, goal: Vec2, agent_radius: f32) -> Option<Self> {
+        let goal = nav_grid.cell_at(goal)?;
+        if !nav_grid.is_walkable(goal, agent_radius) {
+            return None;
+        }
+
+        let size = nav_grid.size();
+        let index = |cell: UVec2| (cell.y * size.x + cell.x) as usize;
+        let mut integration = vec![f32::INFINITY; (size.x * size.y) as usize];
+        let mut open = BinaryHeap::new();
+
+        integration[index(goal)] = 0.0;
+        open.push(OpenCell {
+            cell: goal,
+            cost: 0.0,
+        });
+
+        while let Some(OpenCell { cell, cost }) = open.pop() {
+            if cost > integration[index(cell)] {
+                continue;
+            }
+
+            for (neighbour, step) in nav_grid.neighbours(cell, agent_radius) {
+                let neighbour_cost = cost + step;
+                if neighbour_cost < integration[index(neighbour)] {
+                    integration[index(neighbour)] = neighbour_cost;
+                    open.push(OpenCell {
+                        cell: neighbour,
+                        cost: neighbour_cost,
+                    });
+                }
+            }
+        }
+
+        // Cells agents cannot stand on, e.g. when pushed against a wall, still
+        // point at their cheapest walkable neighbour to lead them back out.
+        let directions = (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| UVec2::new(x, y)))
+            .map(|cell| {
+                nav_grid
+                    .neighbours(cell, agent_radius)
+                    .map(|(neighbour, _)| neighbour)
+                    .filter(|&neighbour| integration[index(neighbour)] < integration[index(cell)])
+                    .min_by(|&a, &b| integration[index(a)].total_cmp(&integration[index(b)]))
+                    .map_or(Vec2::ZERO, |neighbour| {
+                        (neighbour.as_vec2() - cell.as_vec2()).normalize()
+                    })
+            })
+            .collect();
+
+        Some(Self {
+            size,
+            integration,
+            directions,
+        })
+    }
+
+    /// Unit direction towards the goal, or zero at the goal and where it cannot be reached.
+    pub fn direction(&self, cell: UVec2) -> Vec2 {
+        self.directions[self.index(cell)]
+    }
+
+    /// Cost of reaching the goal from `cell`, infinite if it cannot be reached.
+    pub fn cost(&self, cell: UVec2) -> f32 {
+        self.integration[self.index(cell)]
+    }
+
+    fn index(&self, cell: UVec2) -> usize {
+        (cell.y * self.size.x + cell.x) as usize
+    }
+}
+
+struct OpenCell {
+    cell: UVec2,
+    cost: f32,
+}
+
+impl PartialEq for OpenCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for OpenCell {}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenCell {
+    // Reversed so the `BinaryHeap` pops the cheapest cell first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 20x20 cells of 10 units, centred on the origin.
+    fn grid() -> NavGrid {
+        NavGrid::new(Vec2::splat(-100.0), UVec2::splat(20), 10.0, 50.0)
+    }
+
+    #[test]
+    fn points_towards_goal_on_empty_grid() {
+        let grid = grid();
+        let field = FlowField::build(&grid, grid.cell_center(UVec2::new(10, 10)), 5.0).unwrap();
+
+        assert_eq!(field.direction(UVec2::new(2, 10)), Vec2::X);
+        assert_eq!(field.direction(UVec2::new(10, 18)), Vec2::NEG_Y);
+        assert_eq!(
+            field.direction(UVec2::new(5, 5)),
+            Vec2::new(1.0, 1.0).normalize()
+        );
+        assert_eq!(field.direction(UVec2::new(10, 10)), Vec2::ZERO);
+    }
+
+    #[test]
+    fn flows_around_walls() {
+        let mut grid = grid();
+        grid.insert_obstacle(Entity::from_raw(1), (0..=15).map(|y| UVec2::new(10, y)));
+        let field = FlowField::build(&grid, grid.cell_center(UVec2::new(15, 2)), 5.0).unwrap();
+
+        // Straight across is blocked, so the cheapest way is up and over the wall.
+        let direction = field.direction(UVec2::new(5, 2));
+        assert!(direction.y > 0.0);
+        assert!(field.cost(UVec2::new(5, 2)) > 10.0);
+    }
+
+    #[test]
+    fn enclosed_cells_have_no_direction() {
+        let mut grid = grid();
+        let ring = (3..=7).flat_map(|i| {
+            [
+                UVec2::new(i, 3),
+                UVec2::new(i, 7),
+                UVec2::new(3, i),
+                UVec2::new(7, i),
+            ]
+        });
+        grid.insert_obstacle(Entity::from_raw(1), ring);
+        let field = FlowField::build(&grid, grid.cell_center(UVec2::new(15, 15)), 5.0).unwrap();
+
+        assert_eq!(field.direction(UVec2::new(5, 5)), Vec2::ZERO);
+        assert!(field.cost(UVec2::new(5, 5)).is_infinite());
+    }
+}
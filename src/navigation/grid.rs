@@ -0,0 +1,450 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::f32::consts::SQRT_2;
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+const NEIGHBOURS: [(i32, i32); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Grid of walkable cells covering the arena, built from static colliders.
+///
+/// Every cell knows how far it is from the closest blocked cell, so one grid
+/// answers queries for agents of any radius up to `max_clearance`.
+#[derive(Resource, Debug, Clone)]
+pub struct NavGrid {
+    /// World position of the lower left corner of cell `(0, 0)`.
+    origin: Vec2,
+    cell_size: f32,
+    size: UVec2,
+    /// Clearance is not tracked beyond this distance.
+    max_clearance: f32,
+    /// Number of obstacles covering each cell.
+    blockers: Vec<u16>,
+    clearance: Vec<f32>,
+    obstacles: HashMap<Entity, Vec<usize>>,
+}
+
+impl NavGrid {
+    pub fn new(origin: Vec2, size: UVec2, cell_size: f32, max_clearance: f32) -> Self {
+        let cells = (size.x * size.y) as usize;
+
+        Self {
+            origin,
+            cell_size,
+            size,
+            max_clearance,
+            blockers: vec![0; cells],
+            clearance: vec![max_clearance; cells],
+            obstacles: HashMap::default(),
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn size(&self) -> UVec2 {
+        self.size
+    }
+
+    /// The cell containing `position`, if it is inside the grid.
+    pub fn cell_at(&self, position: Vec2) -> Option<UVec2> {
+        let cell = ((position - self.origin) / self.cell_size).floor();
+
+        (cell.x >= 0.0
+            && cell.y >= 0.0
+            && cell.x < self.size.x as f32
+            && cell.y < self.size.y as f32)
+            .then(|| cell.as_uvec2())
+    }
+
+    /// All cells overlapping the world-space rectangle from `min` to `max`.
+    pub fn cells_in(&self, min: Vec2, max: Vec2) -> impl Iterator<Item = UVec2> {
+        let last = self.size.as_ivec2() - 1;
+        let min = ((min - self.origin) / self.cell_size)
+            .floor()
+            .as_ivec2()
+            .clamp(IVec2::ZERO, last);
+        let max = ((max - self.origin) / self.cell_size)
+            .floor()
+            .as_ivec2()
+            .clamp(IVec2::ZERO, last);
+
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| UVec2::new(x as u32, y as u32)))
+    }
+
+    pub fn cell_center(&self, cell: UVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + 0.5) * self.cell_size
+    }
+
+    /// Distance from the centre of `cell` to the closest blocked cell.
+    pub fn clearance(&self, cell: UVec2) -> f32 {
+        self.clearance[self.index(cell)]
+    }
+
+    pub fn is_blocked(&self, cell: UVec2) -> bool {
+        self.blockers[self.index(cell)] > 0
+    }
+
+    pub fn is_walkable(&self, cell: UVec2, agent_radius: f32) -> bool {
+        !self.is_blocked(cell) && self.clearance(cell) >= agent_radius
+    }
+
+    /// Marks `cells` as covered by `entity`, replacing whatever it covered before.
+    pub fn insert_obstacle(&mut self, entity: Entity, cells: impl IntoIterator<Item = UVec2>) {
+        let mut dirty = self.take_obstacle(entity);
+        let indices: Vec<usize> = cells
+            .into_iter()
+            .filter(|cell| cell.x < self.size.x && cell.y < self.size.y)
+            .map(|cell| self.index(cell))
+            .collect();
+
+        for &index in &indices {
+            self.blockers[index] += 1;
+        }
+
+        dirty.extend(indices.iter().copied());
+        self.obstacles.insert(entity, indices);
+        self.update_clearance(&dirty);
+    }
+
+    pub fn remove_obstacle(&mut self, entity: Entity) {
+        let dirty = self.take_obstacle(entity);
+        self.update_clearance(&dirty);
+    }
+
+    pub fn contains_obstacle(&self, entity: Entity) -> bool {
+        self.obstacles.contains_key(&entity)
+    }
+
+    /// Shortest path for an agent of `agent_radius` from `from` to `to`, as
+    /// waypoints ending at `to`. `None` if `to` cannot be reached.
+    pub fn find_path(&self, from: Vec2, to: Vec2, agent_radius: f32) -> Option<Vec<Vec2>> {
+        let start = self.cell_at(from)?;
+        let goal = self.cell_at(to)?;
+
+        if !self.is_walkable(goal, agent_radius) {
+            return None;
+        }
+
+        let cells = self.search(start, goal, agent_radius)?;
+        let mut waypoints: Vec<Vec2> = cells.iter().map(|&cell| self.cell_center(cell)).collect();
+        waypoints[0] = from;
+        *waypoints.last_mut().unwrap() = to;
+
+        Some(self.smooth(waypoints, agent_radius))
+    }
+
+    /// Whether an agent of `agent_radius` can move in a straight line from `from` to `to`.
+    pub fn is_line_clear(&self, from: Vec2, to: Vec2, agent_radius: f32) -> bool {
+        let steps = ((to - from).length() / (self.cell_size * 0.5))
+            .ceil()
+            .max(1.0) as usize;
+
+        (0..=steps).all(|step| {
+            self.cell_at(from.lerp(to, step as f32 / steps as f32))
+                .is_some_and(|cell| self.is_walkable(cell, agent_radius))
+        })
+    }
+
+    fn search(&self, start: UVec2, goal: UVec2, agent_radius: f32) -> Option<Vec<UVec2>> {
+        let start_index = self.index(start);
+        let goal_index = self.index(goal);
+
+        let mut open = BinaryHeap::new();
+        let mut came_from: HashMap<usize, usize> = HashMap::default();
+        let mut cost = vec![f32::INFINITY; self.blockers.len()];
+
+        cost[start_index] = 0.0;
+        open.push(OpenCell {
+            index: start_index,
+            estimate: octile_distance(start, goal),
+        });
+
+        while let Some(OpenCell { index, .. }) = open.pop() {
+            if index == goal_index {
+                let mut path = vec![self.cell(index)];
+                let mut current = index;
+                while let Some(&previous) = came_from.get(&current) {
+                    path.push(self.cell(previous));
+                    current = previous;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let cell = self.cell(index);
+            for (neighbour, step) in self.neighbours(cell, agent_radius) {
+                let neighbour_index = self.index(neighbour);
+                let neighbour_cost = cost[index] + step;
+
+                if neighbour_cost < cost[neighbour_index] {
+                    cost[neighbour_index] = neighbour_cost;
+                    came_from.insert(neighbour_index, index);
+                    open.push(OpenCell {
+                        index: neighbour_index,
+                        estimate: neighbour_cost + octile_distance(neighbour, goal),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Walkable neighbours of `cell` and the cost of stepping to them, in cells.
+    /// Diagonal steps must not cut the corner of a blocked cell.
+    pub(super) fn neighbours(
+        &self,
+        cell: UVec2,
+        agent_radius: f32,
+    ) -> impl Iterator<Item = (UVec2, f32)> + '_ {
+        let walkable = move |x: i32, y: i32| {
+            x >= 0
+                && y >= 0
+                && x < self.size.x as i32
+                && y < self.size.y as i32
+                && self.is_walkable(UVec2::new(x as u32, y as u32), agent_radius)
+        };
+        let (x, y) = (cell.x as i32, cell.y as i32);
+
+        NEIGHBOURS.into_iter().filter_map(move |(dx, dy)| {
+            let diagonal = dx != 0 && dy != 0;
+
+            if !walkable(x + dx, y + dy)
+                || (diagonal && !(walkable(x + dx, y) && walkable(x, y + dy)))
+            {
+                return None;
+            }
+
+            let step = if diagonal { SQRT_2 } else { 1.0 };
+            Some((UVec2::new((x + dx) as u32, (y + dy) as u32), step))
+        })
+    }
+
+    /// Drops waypoints that can be skipped by driving straight to a later one.
+    fn smooth(&self, waypoints: Vec<Vec2>, agent_radius: f32) -> Vec<Vec2> {
+        let mut smoothed = vec![waypoints[0]];
+        let mut anchor = 0;
+
+        while anchor < waypoints.len() - 1 {
+            let next = (anchor + 1..waypoints.len())
+                .rev()
+                .find(|&candidate| {
+                    self.is_line_clear(waypoints[anchor], waypoints[candidate], agent_radius)
+                })
+                .unwrap_or(anchor + 1);
+
+            smoothed.push(waypoints[next]);
+            anchor = next;
+        }
+
+        smoothed
+    }
+
+    fn take_obstacle(&mut self, entity: Entity) -> Vec<usize> {
+        let indices = self.obstacles.remove(&entity).unwrap_or_default();
+
+        for &index in &indices {
+            self.blockers[index] -= 1;
+        }
+
+        indices
+    }
+
+    /// Recomputes clearance around the cells in `dirty`. Nothing further than
+    /// `max_clearance` from a changed cell can be affected.
+    fn update_clearance(&mut self, dirty: &[usize]) {
+        if dirty.is_empty() {
+            return;
+        }
+
+        let reach = (self.max_clearance / self.cell_size).ceil() as i32 + 1;
+        let mut affected = HashSet::new();
+
+        for &index in dirty {
+            let cell = self.cell(index).as_ivec2();
+            for y in (cell.y - reach).max(0)..=(cell.y + reach).min(self.size.y as i32 - 1) {
+                for x in (cell.x - reach).max(0)..=(cell.x + reach).min(self.size.x as i32 - 1) {
+                    affected.insert(self.index(UVec2::new(x as u32, y as u32)));
+                }
+            }
+        }
+
+        for index in affected {
+            self.clearance[index] = self.compute_clearance(self.cell(index), reach);
+        }
+    }
+
+    fn compute_clearance(&self, cell: UVec2, reach: i32) -> f32 {
+        if self.is_blocked(cell) {
+            return 0.0;
+        }
+
+        let cell = cell.as_ivec2();
+        let mut closest = self.max_clearance;
+
+        for y in (cell.y - reach).max(0)..=(cell.y + reach).min(self.size.y as i32 - 1) {
+            for x in (cell.x - reach).max(0)..=(cell.x + reach).min(self.size.x as i32 - 1) {
+                if self.is_blocked(UVec2::new(x as u32, y as u32)) {
+                    // Distance to the edge of the blocked cell rather than its centre.
+                    let distance =
+                        ((IVec2::new(x, y) - cell).as_vec2().length() - 0.5) * self.cell_size;
+                    closest = closest.min(distance);
+                }
+            }
+        }
+
+        closest
+    }
+
+    fn index(&self, cell: UVec2) -> usize {
+        (cell.y * self.size.x + cell.x) as usize
+    }
+
+    fn cell(&self, index: usize) -> UVec2 {
+        UVec2::new(index as u32 % self.size.x, index as u32 / self.size.x)
+    }
+}
+
+impl Default for NavGrid {
+    fn default() -> Self {
+        Self::new(Vec2::splat(-2000.0), UVec2::splat(200), 20.0, 100.0)
+    }
+}
+
+fn octile_distance(a: UVec2, b: UVec2) -> f32 {
+    let delta = (a.as_ivec2() - b.as_ivec2()).abs().as_vec2();
+    delta.max_element() + (SQRT_2 - 1.0) * delta.min_element()
+}
+
+struct OpenCell {
+    index: usize,
+    estimate: f32,
+}
+
+impl PartialEq for OpenCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl Eq for OpenCell {}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenCell {
+    // Reversed so the `BinaryHeap` pops the lowest estimate first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 20x20 cells of 10 units, centred on the origin.
+    fn grid() -> NavGrid {
+        NavGrid::new(Vec2::splat(-100.0), UVec2::splat(20), 10.0, 50.0)
+    }
+
+    /// A vertical wall at cell column 10 from row `from` to row `to`, inclusive.
+    fn wall(from: u32, to: u32) -> impl Iterator<Item = UVec2> {
+        (from..=to).map(|y| UVec2::new(10, y))
+    }
+
+    #[test]
+    fn straight_path_on_empty_grid() {
+        let path = grid()
+            .find_path(Vec2::new(-80.0, 0.0), Vec2::new(80.0, 0.0), 5.0)
+            .unwrap();
+
+        assert_eq!(path, vec![Vec2::new(-80.0, 0.0), Vec2::new(80.0, 0.0)]);
+    }
+
+    #[test]
+    fn path_goes_around_wall() {
+        let mut grid = grid();
+        grid.insert_obstacle(Entity::from_raw(1), wall(0, 15));
+
+        let from = Vec2::new(-80.0, -80.0);
+        let to = Vec2::new(80.0, -80.0);
+        let path = grid.find_path(from, to, 5.0).unwrap();
+
+        assert!(path.len() > 2);
+        assert!(path
+            .windows(2)
+            .all(|pair| grid.is_line_clear(pair[0], pair[1], 5.0)));
+        assert!(path.iter().any(|waypoint| waypoint.y > 60.0));
+    }
+
+    #[test]
+    fn wide_agents_do_not_fit_through_narrow_gaps() {
+        let mut grid = grid();
+        grid.insert_obstacle(Entity::from_raw(1), wall(0, 8));
+        grid.insert_obstacle(Entity::from_raw(2), wall(11, 19));
+
+        let from = Vec2::new(-80.0, 0.0);
+        let to = Vec2::new(80.0, 0.0);
+
+        assert!(grid.find_path(from, to, 5.0).is_some());
+        assert!(grid.find_path(from, to, 20.0).is_none());
+    }
+
+    #[test]
+    fn removing_obstacle_reopens_path() {
+        let mut grid = grid();
+        let wall_entity = Entity::from_raw(1);
+        grid.insert_obstacle(wall_entity, wall(0, 19));
+
+        let from = Vec2::new(-80.0, 0.0);
+        let to = Vec2::new(80.0, 0.0);
+        assert!(grid.find_path(from, to, 5.0).is_none());
+
+        grid.remove_obstacle(wall_entity);
+        assert!(grid.find_path(from, to, 5.0).is_some());
+        assert_eq!(grid.clearance(UVec2::new(10, 10)), 50.0);
+    }
+
+    #[test]
+    fn moving_obstacle_replaces_its_cells() {
+        let mut grid = grid();
+        let entity = Entity::from_raw(1);
+        grid.insert_obstacle(entity, [UVec2::new(5, 5)]);
+        grid.insert_obstacle(entity, [UVec2::new(6, 5)]);
+
+        assert!(!grid.is_blocked(UVec2::new(5, 5)));
+        assert!(grid.is_blocked(UVec2::new(6, 5)));
+    }
+
+    #[test]
+    fn unreachable_or_outside_goals_have_no_path() {
+        let mut grid = grid();
+        grid.insert_obstacle(Entity::from_raw(1), [UVec2::new(15, 10)]);
+
+        assert!(grid
+            .find_path(Vec2::ZERO, grid.cell_center(UVec2::new(15, 10)), 5.0)
+            .is_none());
+        assert!(grid
+            .find_path(Vec2::ZERO, Vec2::new(500.0, 0.0), 5.0)
+            .is_none());
+    }
+}
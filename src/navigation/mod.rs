@@ -0,0 +1,31 @@
+pub mod components;
+pub mod flow_field;
+pub mod grid;
+mod systems;
+
+use bevy::prelude::*;
+
+use components::*;
+use grid::NavGrid;
+use systems::*;
+
+use crate::GameplaySet;
+
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavGrid>()
+            .init_resource::<SwarmFlowField>()
+            .init_resource::<FlowFieldSettings>()
+            .add_system(update_nav_obstacles.before(rebuild_flow_field))
+            .add_system(rebuild_flow_field)
+            .add_system(
+                steer_flow_field_agents
+                    .after(rebuild_flow_field)
+                    .in_set(GameplaySet),
+            )
+            .add_system(toggle_flow_field_arrows)
+            .add_system(draw_flow_field_arrows.after(rebuild_flow_field));
+    }
+}
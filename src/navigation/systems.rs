@@ -0,0 +1,188 @@
+use std::f32::consts::FRAC_PI_6;
+
+use bevy::prelude::*;
+use bevy_prototype_debug_lines::DebugLines;
+use bevy_rapier2d::prelude::*;
+use input_map::actions::{Action, ActionState};
+
+use super::components::*;
+use super::flow_field::FlowField;
+use super::grid::NavGrid;
+
+/// How quickly agents match the velocity the flow field asks for.
+const AGENT_RESPONSE: f32 = 5.0;
+/// Arrows are only drawn this far around the goal.
+const ARROW_RANGE: f32 = 400.0;
+const ARROW_HEAD_ANGLE: f32 = FRAC_PI_6;
+
+type ChangedColliders<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Collider,
+        &'static GlobalTransform,
+        Option<&'static RigidBody>,
+    ),
+    (
+        Without<Sensor>,
+        Or<(Changed<Collider>, Changed<GlobalTransform>)>,
+    ),
+>;
+
+/// Keeps the grid in sync with static colliders: those without a rigid body or
+/// with a fixed one. Only colliders that changed since the last run are rasterized.
+pub fn update_nav_obstacles(
+    mut nav_grid: ResMut<NavGrid>,
+    collider_query: ChangedColliders,
+    mut removed_colliders: RemovedComponents<Collider>,
+) {
+    // Most removed colliders are shells and debris, which never touched the grid.
+    for entity in removed_colliders.iter() {
+        if nav_grid.contains_obstacle(entity) {
+            nav_grid.remove_obstacle(entity);
+        }
+    }
+
+    for (entity, collider, global_transform, rigid_body) in collider_query.iter() {
+        if rigid_body.is_some_and(|rigid_body| *rigid_body != RigidBody::Fixed) {
+            if nav_grid.contains_obstacle(entity) {
+                nav_grid.remove_obstacle(entity);
+            }
+            continue;
+        }
+
+        let cells = covered_cells(&nav_grid, collider, global_transform);
+        nav_grid.insert_obstacle(entity, cells);
+    }
+}
+
+/// Cells whose centre is within half a cell of the collider.
+fn covered_cells(
+    nav_grid: &NavGrid,
+    collider: &Collider,
+    global_transform: &GlobalTransform,
+) -> Vec<UVec2> {
+    let (_, rotation, translation) = global_transform.to_scale_rotation_translation();
+    let translation = translation.truncate();
+    let angle = rotation.to_euler(EulerRot::ZYX).0;
+    let margin = nav_grid.cell_size() * 0.5;
+
+    let aabb = collider.raw.compute_local_aabb();
+    let corners = [
+        Vec2::new(aabb.mins.x, aabb.mins.y),
+        Vec2::new(aabb.mins.x, aabb.maxs.y),
+        Vec2::new(aabb.maxs.x, aabb.mins.y),
+        Vec2::new(aabb.maxs.x, aabb.maxs.y),
+    ]
+    .map(|corner| translation + Vec2::from_angle(angle).rotate(corner));
+    let min = corners.into_iter().reduce(Vec2::min).unwrap() - margin;
+    let max = corners.into_iter().reduce(Vec2::max).unwrap() + margin;
+
+    nav_grid
+        .cells_in(min, max)
+        .filter(|&cell| {
+            let center = nav_grid.cell_center(cell);
+            let projection = collider.project_point(translation, angle, center, true);
+            projection.is_inside || projection.point.distance(center) <= margin
+        })
+        .collect()
+}
+
+pub fn rebuild_flow_field(
+    nav_grid: Res<NavGrid>,
+    settings: Res<FlowFieldSettings>,
+    mut flow_field: ResMut<SwarmFlowField>,
+    goal_query: Query<&GlobalTransform, With<FlowFieldGoal>>,
+) {
+    let Some(goal) = goal_query
+        .iter()
+        .next()
+        .map(|transform| transform.translation().truncate())
+    else {
+        if flow_field.field.is_some() {
+            *flow_field = SwarmFlowField::default();
+        }
+        return;
+    };
+
+    let goal_moved = flow_field
+        .built_for
+        .is_none_or(|built_for| built_for.distance(goal) > settings.rebuild_distance);
+
+    if goal_moved || nav_grid.is_changed() || settings.is_changed() {
+        flow_field.field = FlowField::build(&nav_grid, goal, settings.agent_radius);
+        flow_field.built_for = Some(goal);
+    }
+}
+
+pub fn steer_flow_field_agents(
+    time: Res<Time>,
+    nav_grid: Res<NavGrid>,
+    flow_field: Res<SwarmFlowField>,
+    mut agent_query: Query<(&FlowFieldAgent, &Transform, &mut Velocity)>,
+) {
+    let Some(field) = &flow_field.field else {
+        return;
+    };
+    let response = (AGENT_RESPONSE * time.delta_seconds()).min(1.0);
+
+    for (agent, transform, mut velocity) in agent_query.iter_mut() {
+        let direction = nav_grid
+            .cell_at(transform.translation.truncate())
+            .map_or(Vec2::ZERO, |cell| field.direction(cell));
+
+        velocity.linvel = velocity.linvel.lerp(direction * agent.speed, response);
+    }
+}
+
+pub fn toggle_flow_field_arrows(
+    actions: Res<ActionState>,
+    mut settings: ResMut<FlowFieldSettings>,
+) {
+    if actions.just_pressed(Action::ToggleFlowField) {
+        settings.show_arrows = !settings.show_arrows;
+    }
+}
+
+pub fn draw_flow_field_arrows(
+    nav_grid: Res<NavGrid>,
+    settings: Res<FlowFieldSettings>,
+    flow_field: Res<SwarmFlowField>,
+    mut lines: ResMut<DebugLines>,
+) {
+    let (true, Some(field), Some(goal)) = (
+        settings.show_arrows,
+        &flow_field.field,
+        flow_field.built_for,
+    ) else {
+        return;
+    };
+
+    let range = Vec2::splat(ARROW_RANGE);
+    let length = nav_grid.cell_size() * 0.4;
+
+    for cell in nav_grid.cells_in(goal - range, goal + range) {
+        let direction = field.direction(cell);
+        if direction == Vec2::ZERO {
+            continue;
+        }
+
+        // Green next to the goal, fading to red further away.
+        let distance = field.cost(cell) * nav_grid.cell_size() / (ARROW_RANGE * 2.0);
+        let color = Color::rgb(distance.min(1.0), (1.0 - distance).max(0.0), 0.0);
+
+        let center = nav_grid.cell_center(cell);
+        let tip = center + direction * length;
+        lines.line_colored(
+            (center - direction * length).extend(0.),
+            tip.extend(0.),
+            0.0,
+            color,
+        );
+        for side in [-ARROW_HEAD_ANGLE, ARROW_HEAD_ANGLE] {
+            let head = Vec2::from_angle(side).rotate(-direction) * length * 0.5;
+            lines.line_colored(tip.extend(0.), (tip + head).extend(0.), 0.0, color);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+mod components;
+mod styles;
+mod systems;
+
+use systems::interactions::*;
+use systems::layout::*;
+
+use bevy::prelude::*;
+
+use crate::menu::systems::interact_with_menu_buttons;
+use crate::{SettingsState, SimulationState};
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // OnEnter State Systems
+            .add_system(spawn_pause_menu.in_schedule(OnEnter(SimulationState::Paused)))
+            // The settings screen takes the place of the menu while it is open.
+            .add_system(despawn_pause_menu.in_schedule(OnEnter(SettingsState::Open)))
+            .add_system(
+                spawn_pause_menu
+                    .in_schedule(OnExit(SettingsState::Open))
+                    .run_if(in_state(SimulationState::Paused)),
+            )
+            // Systems
+            .add_system(
+                handle_pause_menu_buttons
+                    .after(interact_with_menu_buttons)
+                    .in_set(OnUpdate(SimulationState::Paused)),
+            )
+            // OnExit State Systems
+            .add_system(despawn_pause_menu.in_schedule(OnExit(SimulationState::Paused)));
+    }
+}
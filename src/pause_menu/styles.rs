@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+pub const PAUSE_MENU_BACKGROUND_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.6);
+
+pub const PAUSE_MENU_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    flex_direction: FlexDirection::Column,
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+    gap: Size::new(Val::Px(8.0), Val::Px(8.0)),
+    ..Style::DEFAULT
+};
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use crate::menu::{components::MenuAction, events::MenuButtonPressed};
+use crate::{AppState, SettingsState, SimulationState};
+
+pub fn handle_pause_menu_buttons(
+    mut pressed_events: EventReader<MenuButtonPressed>,
+    mut simulation_state_next_state: ResMut<NextState<SimulationState>>,
+    mut settings_state_next_state: ResMut<NextState<SettingsState>>,
+    mut app_state_next_state: ResMut<NextState<AppState>>,
+) {
+    for pressed in pressed_events.iter() {
+        match pressed.action {
+            MenuAction::Resume => simulation_state_next_state.set(SimulationState::Running),
+            MenuAction::Settings => settings_state_next_state.set(SettingsState::Open),
+            MenuAction::QuitToMenu => app_state_next_state.set(AppState::MainMenu),
+            _ => {}
+        }
+    }
+}
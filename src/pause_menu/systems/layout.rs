@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::menu::components::MenuAction;
+use crate::menu::screen::{MenuItem, MenuScreen};
+use crate::pause_menu::components::*;
+use crate::pause_menu::styles::*;
+
+pub fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    build_pause_menu(&mut commands, &asset_server);
+}
+
+pub fn despawn_pause_menu(
+    mut commands: Commands,
+    pause_menu_query: Query<Entity, With<PauseMenu>>,
+) {
+    if let Ok(pause_menu_entity) = pause_menu_query.get_single() {
+        commands.entity(pause_menu_entity).despawn_recursive();
+    }
+}
+
+pub fn build_pause_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    MenuScreen {
+        title: "Paused",
+        style: PAUSE_MENU_STYLE,
+        background_color: PAUSE_MENU_BACKGROUND_COLOR,
+        items: vec![
+            MenuItem::button("Resume", MenuAction::Resume),
+            MenuItem::button("Settings", MenuAction::Settings),
+            MenuItem::button("Main Menu", MenuAction::QuitToMenu),
+        ],
+        ..default()
+    }
+    .spawn(commands, asset_server, PauseMenu {})
+}
@@ -0,0 +1,2 @@
+pub mod interactions;
+pub mod layout;
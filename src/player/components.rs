@@ -6,6 +6,7 @@ use bevy_inspector_egui::prelude::*;
 )]
 #[reflect(Component, PartialEq)]
 pub struct Player {
+    pub control: ControlMode,
     #[inspector(min = 0.0, max = 1000.0)]
     pub speed: f32,
     pub pull: Transform,
@@ -13,6 +14,17 @@ pub struct Player {
     pub pull_distance: f32,
 }
 
+/// How the player's input moves the hull.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(PartialEq)]
+pub enum ControlMode {
+    /// Throttle and hull rotation through `Tracks`.
+    #[default]
+    Tracks,
+    /// Strafe in any direction by dragging the hull with the `PlayerPull` body.
+    Pull,
+}
+
 #[derive(
     Copy, Clone, Debug, Default, PartialEq, Component, Reflect, FromReflect, InspectorOptions,
 )]
@@ -22,5 +34,15 @@ pub struct PlayerPull {
     pub speed: f32,
 }
 
+/// What the player aims the turret with. Moving the mouse switches to the cursor, pushing
+/// the aim stick switches to the stick.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub enum PlayerAim {
+    #[default]
+    Cursor,
+    /// The stick's direction, relative to the hull: up is the way the hull points.
+    Stick(Vec2),
+}
+
 #[derive(Component, Default)]
 pub struct WorldCoords(pub Vec2);
@@ -1,19 +1,25 @@
 pub mod components;
 mod systems;
 
-use bevy::prelude::{App, Plugin};
+use bevy::prelude::*;
 
 use components::*;
 use systems::*;
 
+use crate::GameplaySet;
+
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Player>()
-            // .add_system(player_movement)
-            .add_system(player_pull_movement)
-            .add_system(player_raycast)
-            .add_system(player_look_at);
+            .init_resource::<PlayerAim>()
+            // .add_system(player_movement.in_set(GameplaySet))
+            .add_system(attach_player_controls.in_set(GameplaySet))
+            .add_system(player_track_input.in_set(GameplaySet))
+            .add_system(player_pull_movement.in_set(GameplaySet))
+            .add_system(player_fire.in_set(GameplaySet))
+            .add_system(player_look_at.in_set(GameplaySet))
+            .add_system(player_aim_reticle.in_set(GameplaySet));
     }
 }
@@ -1,143 +1,203 @@
-use bevy::{input::mouse::MouseButtonInput, prelude::*, window::PrimaryWindow};
+use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_prototype_debug_lines::DebugLines;
-use bevy_rapier2d::prelude::{ExternalImpulse, QueryFilter, RapierContext, Velocity};
-
-use crate::{first::components::LookAt, MainCamera};
+use bevy_rapier2d::prelude::*;
+use input_map::actions::{Action, ActionState};
+
+use crate::{
+    first::components::LookAt,
+    gun::{
+        components::{Gun, Turret},
+        events::FireGun,
+    },
+    systems::get_cursor_position,
+    tracks::components::Tracks,
+    GameSessionEntity, MainCamera,
+};
 
 use super::components::*;
 
-pub fn player_pull_movement(
-    keyboard_input: Res<Input<KeyCode>>,
-    mut player_query: Query<(&mut PlayerPull, &mut Velocity)>,
-    time: Res<Time>,
+/// Radians of aim lag at which the reticle shows the gun as on target.
+const AIM_TOLERANCE: f32 = 0.02;
+/// How far the aim stick has to be pushed to take over from the cursor.
+const AIM_STICK_DEADZONE: f32 = 0.3;
+/// How far ahead of the turret the stick aims.
+const AIM_STICK_DISTANCE: f32 = 300.0;
+
+/// Hooks the player's controls up to their tank once it has been built.
+type NewPlayerHull<'a> = (Entity, &'a Transform, &'a Children);
+
+pub fn attach_player_controls(
+    mut commands: Commands,
+    player_query: Query<NewPlayerHull, (With<Player>, Added<RigidBody>)>,
+    gun_query: Query<(), With<Gun>>,
 ) {
-    for (player, mut velocity) in player_query.iter_mut() {
-        let mut direction = Vec2::ZERO;
-
-        let key_directions = [
-            (KeyCode::Left, Vec2::new(-1.0, 0.0)),
-            (KeyCode::A, Vec2::new(-1.0, 0.0)),
-            (KeyCode::Right, Vec2::new(1.0, 0.0)),
-            (KeyCode::D, Vec2::new(1.0, 0.0)),
-            (KeyCode::Up, Vec2::new(0.0, 1.0)),
-            (KeyCode::W, Vec2::new(0.0, 1.0)),
-            (KeyCode::Down, Vec2::new(0.0, -1.0)),
-            (KeyCode::S, Vec2::new(0.0, -1.0)),
-        ];
-
-        key_directions.iter().for_each(|(key_code, dir)| {
-            if keyboard_input.pressed(*key_code) {
-                direction += *dir;
+    for (body, transform, children) in player_query.iter() {
+        for child in children.iter() {
+            if gun_query.contains(*child) {
+                commands.entity(*child).insert(LookAt);
             }
-        });
-
-        if direction.length() > 0.0 {
-            direction = direction.normalize()
         }
 
-        let speed = player.speed;
-        let delta_time = time.delta_seconds();
-
-        velocity.linvel = direction * speed * delta_time;
+        let joint_controller = RevoluteJointBuilder::new()
+            .local_anchor1(Vec2::new(10.0, 0.0))
+            .local_anchor2(Vec2::new(0.0, 0.0));
+
+        commands.spawn((
+            Name::new("Player Controller"),
+            GameSessionEntity,
+            ImpulseJoint::new(body, joint_controller),
+            RigidBody::Dynamic,
+            Collider::ball(5.),
+            GlobalTransform::default(),
+            Transform::from_translation(transform.transform_point(Vec3::new(10.0, 0.0, 0.0))),
+            Velocity::default(),
+            PlayerPull { speed: 15000. },
+            Damping {
+                linear_damping: 1.0,
+                angular_damping: 100.,
+            },
+            ColliderMassProperties::Mass(0.5),
+            CollisionGroups::new(Group::NONE, Group::NONE),
+        ));
     }
 }
 
-use bevy::input::ButtonState;
-
-fn look_at_z(
-    local_transform: &mut Transform,
-    global_transform: &GlobalTransform,
-    cursor_position: &Vec2,
+pub fn player_track_input(
+    actions: Res<ActionState>,
+    mut player_query: Query<(&Player, &mut Tracks)>,
 ) {
-    let global_transform = global_transform.compute_transform();
+    for (player, mut tracks) in player_query.iter_mut() {
+        tracks.engaged = player.control == ControlMode::Tracks;
 
-    let turret_position = global_transform.translation.truncate();
-    let direction = *cursor_position - turret_position;
-    let angle = direction.y.atan2(direction.x);
+        let throttle = actions.axis(Action::MoveBackward, Action::MoveForward);
+        let steer = actions.axis(Action::RotateHullRight, Action::RotateHullLeft);
 
-    let parent_rotation = global_transform.rotation * local_transform.rotation.inverse();
-    local_transform.rotation = parent_rotation.inverse() * Quat::from_rotation_z(angle);
+        tracks.throttle = f32::clamp(throttle, -1.0, 1.0);
+        tracks.steer = f32::clamp(steer, -1.0, 1.0);
+    }
 }
 
-pub fn player_look_at(
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
-    mut turret_query: Query<(&mut Transform, &GlobalTransform), With<LookAt>>,
+pub fn player_pull_movement(
+    actions: Res<ActionState>,
+    mut pull_query: Query<(&mut PlayerPull, &mut Velocity, &ImpulseJoint)>,
+    player_query: Query<&Player>,
+    time: Res<Time>,
 ) {
-    let window = window_query.single();
-    let (camera, camera_transform) = camera_q.single();
-    let cursor_position = get_cursor_position(window, camera, camera_transform);
+    for (player, mut velocity, joint) in pull_query.iter_mut() {
+        let pulls_hull = player_query
+            .get(joint.parent)
+            .is_ok_and(|player| player.control == ControlMode::Pull);
 
-    for (mut local_transform, global_transform) in turret_query.iter_mut() {
-        if let Some(cursor_position) = cursor_position {
-            look_at_z(&mut local_transform, global_transform, &cursor_position);
+        if !pulls_hull {
+            continue;
         }
+
+        // Pulling moves along the screen axes rather than the hull's.
+        let direction = Vec2::new(
+            actions.axis(Action::RotateHullLeft, Action::RotateHullRight),
+            actions.axis(Action::MoveBackward, Action::MoveForward),
+        )
+        .clamp_length_max(1.0);
+
+        let speed = player.speed;
+        let delta_time = time.delta_seconds();
+
+        velocity.linvel = direction * speed * delta_time;
     }
 }
 
-pub fn player_raycast(
-    mut mousebtn_evr: EventReader<MouseButtonInput>,
+pub fn player_look_at(
     window_query: Query<&Window, With<PrimaryWindow>>,
     camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
-    mut commands: Commands,
-    rapier_context: ResMut<RapierContext>,
-    mut lines: ResMut<DebugLines>,
-    player_query: Query<(Entity, &Transform, &Player)>,
+    actions: Res<ActionState>,
+    mut cursor_moved_events: EventReader<CursorMoved>,
+    mut aim: ResMut<PlayerAim>,
+    mut turret_query: Query<(&mut Turret, &GlobalTransform, &Parent), With<LookAt>>,
+    hull_query: Query<&GlobalTransform, Without<Turret>>,
 ) {
+    let stick = Vec2::new(
+        actions.axis(Action::AimLeft, Action::AimRight),
+        actions.axis(Action::AimDown, Action::AimUp),
+    );
+
+    if stick.length() > AIM_STICK_DEADZONE {
+        *aim = PlayerAim::Stick(stick.normalize());
+    } else if cursor_moved_events.iter().count() > 0 && *aim != PlayerAim::Cursor {
+        *aim = PlayerAim::Cursor;
+    }
+
     let window = window_query.single();
     let (camera, camera_transform) = camera_q.single();
     let cursor_position = get_cursor_position(window, camera, camera_transform);
 
-    for ev in mousebtn_evr.iter() {
-        if ev.state == ButtonState::Pressed {
-            if let Some(cursor_position) = cursor_position {
-                lines.line(
-                    Vec3::from((cursor_position, 0.)),
-                    Vec3::from((cursor_position + Vec2::new(10., 0.), 0.)),
-                    0.0,
-                );
-                for (entity, transform, _) in player_query.iter() {
-                    let ray_pos = Vec2::from((transform.translation.x, transform.translation.y));
-                    let diff = cursor_position - ray_pos;
-                    let ray_dir = diff.normalize();
-                    let ray_pos = ray_pos + ray_dir * 30.;
-
-                    let max_toi = diff.length();
-                    let solid = true;
-                    let filter = QueryFilter::default().exclude_rigid_body(entity);
-
-                    lines.line(
-                        ray_pos.extend(0.),
-                        Vec3::from((ray_pos + ray_dir * max_toi, 0.)),
-                        0.0,
-                    );
-
-                    if let Some((entity, toi)) =
-                        rapier_context.cast_ray(ray_pos, ray_dir, max_toi, solid, filter)
-                    {
-                        // The first collider hit has the entity `entity` and it hit after
-                        // the ray travelled a distance equal to `ray_dir * toi`.
-                        let hit_point = ray_pos + ray_dir * toi;
-
-                        commands.entity(entity).insert(ExternalImpulse::at_point(
-                            ray_dir * 100.,
-                            hit_point,
-                            Vec2::ZERO,
-                        ));
-                    }
+    for (mut turret, global_transform, parent) in turret_query.iter_mut() {
+        match *aim {
+            PlayerAim::Cursor => {
+                if cursor_position.is_some() {
+                    turret.target = cursor_position;
                 }
             }
-        };
+            PlayerAim::Stick(direction) => {
+                let Ok(hull_transform) = hull_query.get(parent.get()) else {
+                    continue;
+                };
+                // Pushing up aims where the hull points, like driving forward.
+                let (_, hull_rotation, _) = hull_transform.to_scale_rotation_translation();
+                let relative = Vec3::new(direction.y, -direction.x, 0.0);
+                let direction = (hull_rotation * relative).truncate();
+
+                let origin = global_transform.translation().truncate();
+                turret.target = Some(origin + direction * AIM_STICK_DISTANCE);
+            }
+        }
     }
 }
 
-fn get_cursor_position(
-    window: &Window,
-    camera: &Camera,
-    camera_transform: &GlobalTransform,
-) -> Option<Vec2> {
-    window
-        .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
-        .map(|ray| ray.origin.truncate())
+/// Marks where the gun actually points, at the cursor's distance, so the aim lag is visible.
+pub fn player_aim_reticle(
+    mut lines: ResMut<DebugLines>,
+    turret_query: Query<(&Turret, &GlobalTransform), With<LookAt>>,
+) {
+    for (turret, global_transform) in turret_query.iter() {
+        if let Some(target) = turret.target {
+            let (_, rotation, translation) = global_transform.to_scale_rotation_translation();
+            let origin = translation.truncate();
+            let direction = (rotation * Vec3::X).truncate();
+            let reticle = origin + direction * origin.distance(target);
+            let size = if turret.is_on_target(AIM_TOLERANCE) {
+                4.0
+            } else {
+                8.0
+            };
+
+            lines.line(
+                (reticle - direction * size).extend(0.),
+                (reticle + direction * size).extend(0.),
+                0.0,
+            );
+            lines.line(
+                (reticle - direction.perp() * size).extend(0.),
+                (reticle + direction.perp() * size).extend(0.),
+                0.0,
+            );
+        }
+    }
+}
+
+/// Holds the trigger of the player's guns for as long as `Action::Fire` is held.
+pub fn player_fire(
+    actions: Res<ActionState>,
+    mut fire_events: EventWriter<FireGun>,
+    gun_query: Query<(Entity, &Parent), With<Gun>>,
+    player_query: Query<(), With<Player>>,
+) {
+    if !actions.pressed(Action::Fire) {
+        return;
+    }
+
+    for (gun, parent) in gun_query.iter() {
+        if player_query.contains(parent.get()) {
+            fire_events.send(FireGun { gun });
+        }
+    }
 }
@@ -0,0 +1,172 @@
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+use serde::{Deserialize, Serialize};
+
+/// The settings the game ships with, relative to the working directory.
+pub const SETTINGS_PATH: &str = "assets/config/settings.ron";
+
+/// Where the player's own settings are kept, in their config directory.
+pub fn user_settings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("tanki").join("settings.ron"))
+}
+
+const MSAA_SAMPLES: [u32; 4] = [1, 2, 4, 8];
+const FRAME_LIMITS: [FrameLimit; 6] = [
+    FrameLimit::Auto,
+    FrameLimit::Fps(30),
+    FrameLimit::Fps(60),
+    FrameLimit::Fps(120),
+    FrameLimit::Fps(144),
+    FrameLimit::Off,
+];
+const WINDOW_MODES: [WindowMode; 3] = [
+    WindowMode::Windowed,
+    WindowMode::BorderlessFullscreen,
+    WindowMode::Fullscreen,
+];
+const VOLUME_STEP: f32 = 0.1;
+const MAX_ZOOM_OUTS: [f32; 4] = [2.0, 4.0, 8.0, 16.0];
+
+/// How fast frames are paced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameLimit {
+    /// Matches the refresh rate of the monitor.
+    Auto,
+    Fps(u32),
+    Off,
+}
+
+/// Graphics, audio and camera options, applied whenever they change.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    /// Samples per pixel, 1 turns anti-aliasing off.
+    pub msaa_samples: u32,
+    pub frame_limit: FrameLimit,
+    pub window_mode: WindowMode,
+    /// From 0 to 1.
+    pub volume: f32,
+    pub zoom_to_cursor: bool,
+    /// How far the camera zooms out, as a multiple of the default view.
+    pub max_zoom_out: f32,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            msaa_samples: 4,
+            frame_limit: FrameLimit::Auto,
+            window_mode: WindowMode::Windowed,
+            volume: 1.0,
+            zoom_to_cursor: true,
+            max_zoom_out: 4.0,
+        }
+    }
+}
+
+impl GameSettings {
+    /// Options missing from the file keep their defaults.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+        ron::from_str(&text).map_err(|error| error.to_string())
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let text = ron::ser::to_string_pretty(self, Default::default())
+            .map_err(|error| error.to_string())?;
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+        }
+        std::fs::write(path, text).map_err(|error| error.to_string())
+    }
+
+    pub fn msaa(&self) -> Msaa {
+        match self.msaa_samples {
+            1 => Msaa::Off,
+            2 => Msaa::Sample2,
+            8 => Msaa::Sample8,
+            _ => Msaa::Sample4,
+        }
+    }
+
+    pub fn next_msaa(&mut self) {
+        self.msaa_samples = next(&MSAA_SAMPLES, &self.msaa_samples);
+    }
+
+    pub fn next_frame_limit(&mut self) {
+        self.frame_limit = next(&FRAME_LIMITS, &self.frame_limit);
+    }
+
+    pub fn next_window_mode(&mut self) {
+        self.window_mode = next(&WINDOW_MODES, &self.window_mode);
+    }
+
+    /// Turns the volume up a step, and back to silent after the loudest.
+    pub fn next_volume(&mut self) {
+        let steps = (self.volume / VOLUME_STEP).round() as u32 + 1;
+        let max_steps = (1.0 / VOLUME_STEP).round() as u32;
+        self.volume = (steps % (max_steps + 1)) as f32 * VOLUME_STEP;
+    }
+
+    pub fn next_max_zoom_out(&mut self) {
+        self.max_zoom_out = next(&MAX_ZOOM_OUTS, &self.max_zoom_out);
+    }
+}
+
+/// The option after `current`, or the first one when `current` is the last or unknown.
+fn next<T: Copy + PartialEq>(options: &[T], current: &T) -> T {
+    let index = options
+        .iter()
+        .position(|option| option == current)
+        .map_or(0, |index| (index + 1) % options.len());
+    options[index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_file_matches_defaults() {
+        let settings: GameSettings =
+            ron::from_str(include_str!("../../assets/config/settings.ron")).unwrap();
+
+        assert_eq!(settings, GameSettings::default());
+    }
+
+    #[test]
+    fn missing_options_keep_their_defaults() {
+        let settings: GameSettings = ron::from_str("(msaa_samples: 8)").unwrap();
+
+        assert_eq!(settings.msaa_samples, 8);
+        assert_eq!(settings.frame_limit, GameSettings::default().frame_limit);
+    }
+
+    #[test]
+    fn options_wrap_around() {
+        let mut settings = GameSettings::default();
+
+        settings.next_msaa();
+        assert_eq!(settings.msaa_samples, 8);
+        settings.next_msaa();
+        assert_eq!(settings.msaa_samples, 1);
+
+        settings.frame_limit = FrameLimit::Off;
+        settings.next_frame_limit();
+        assert_eq!(settings.frame_limit, FrameLimit::Auto);
+    }
+
+    #[test]
+    fn volume_goes_silent_after_the_loudest() {
+        let mut settings = GameSettings::default();
+
+        settings.next_volume();
+        assert_eq!(settings.volume, 0.0);
+        settings.next_volume();
+        assert!((settings.volume - 0.1).abs() < 1e-6);
+    }
+}
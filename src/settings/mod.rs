@@ -0,0 +1,18 @@
+pub mod game_settings;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use game_settings::GameSettings;
+use systems::*;
+
+/// Loads `GameSettings` at startup and applies them whenever they change.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameSettings>()
+            .add_startup_system(load_settings)
+            .add_system(apply_settings.run_if(resource_changed::<GameSettings>()));
+    }
+}
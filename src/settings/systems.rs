@@ -0,0 +1,71 @@
+use bevy::audio::AudioSink;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_framepace::{FramepaceSettings, Limiter};
+use pancam::PanCam;
+
+use super::game_settings::*;
+use crate::MainCamera;
+
+/// Prefers the player's own settings over the ones the game ships with.
+pub fn load_settings(mut settings: ResMut<GameSettings>) {
+    if let Some(path) = user_settings_path().filter(|path| path.exists()) {
+        match GameSettings::load(&path) {
+            Ok(loaded) => {
+                *settings = loaded;
+                return;
+            }
+            Err(error) => warn!("Could not load settings from {}: {error}", path.display()),
+        }
+    }
+
+    match GameSettings::load(SETTINGS_PATH) {
+        Ok(loaded) => *settings = loaded,
+        Err(error) => {
+            warn!("Could not load settings from {SETTINGS_PATH}, using defaults: {error}")
+        }
+    }
+}
+
+pub fn save_settings(settings: &GameSettings) {
+    let Some(path) = user_settings_path() else {
+        warn!("Could not find a config directory to save the settings in");
+        return;
+    };
+
+    if let Err(error) = settings.save(&path) {
+        error!("Could not save settings to {}: {error}", path.display());
+    }
+}
+
+/// Runs whenever the settings change, including once they are loaded. Sounds playing
+/// now are set to the volume, new ones should be played with `GameSettings::volume`.
+pub fn apply_settings(
+    settings: Res<GameSettings>,
+    mut msaa: ResMut<Msaa>,
+    mut framepace_settings: ResMut<FramepaceSettings>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut pan_cam_query: Query<&mut PanCam, With<MainCamera>>,
+) {
+    *msaa = settings.msaa();
+
+    framepace_settings.limiter = match settings.frame_limit {
+        FrameLimit::Auto => Limiter::Auto,
+        FrameLimit::Fps(fps) => Limiter::from_framerate(f64::from(fps)),
+        FrameLimit::Off => Limiter::Off,
+    };
+
+    for (_, sink) in audio_sinks.iter() {
+        sink.set_volume(settings.volume);
+    }
+
+    for mut window in window_query.iter_mut() {
+        window.mode = settings.window_mode;
+    }
+
+    for mut pan_cam in pan_cam_query.iter_mut() {
+        pan_cam.zoom_to_cursor = settings.zoom_to_cursor;
+        pan_cam.max_scale = Some(settings.max_zoom_out);
+    }
+}
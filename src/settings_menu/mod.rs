@@ -0,0 +1,31 @@
+mod components;
+mod styles;
+mod systems;
+
+use systems::interactions::*;
+use systems::layout::*;
+
+use bevy::prelude::*;
+
+use crate::menu::systems::interact_with_menu_buttons;
+use crate::SettingsState;
+
+/// Shows and changes `GameSettings`, in place of the main or pause menu that opened it.
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // OnEnter State Systems
+            .add_system(spawn_settings_menu.in_schedule(OnEnter(SettingsState::Open)))
+            // Systems
+            .add_systems(
+                (handle_settings_menu_buttons, update_settings_labels)
+                    .chain()
+                    .after(interact_with_menu_buttons)
+                    .in_set(OnUpdate(SettingsState::Open)),
+            )
+            // OnExit State Systems
+            .add_system(despawn_settings_menu.in_schedule(OnExit(SettingsState::Open)));
+    }
+}
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+pub const SETTING_FONT_SIZE: f32 = 24.0;
+
+pub const SETTING_BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(400.0), Val::Px(56.0)),
+    ..Style::DEFAULT
+};
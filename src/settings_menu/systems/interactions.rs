@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+
+use crate::menu::{
+    components::{MenuAction, MenuButton},
+    events::MenuButtonPressed,
+    systems::set_button_label,
+};
+use crate::settings::{
+    game_settings::{FrameLimit, GameSettings},
+    systems::save_settings,
+};
+use crate::SettingsState;
+
+/// Changes take effect right away and are saved for the next time the game starts.
+pub fn handle_settings_menu_buttons(
+    mut pressed_events: EventReader<MenuButtonPressed>,
+    mut settings: ResMut<GameSettings>,
+    mut settings_state_next_state: ResMut<NextState<SettingsState>>,
+) {
+    for pressed in pressed_events.iter() {
+        match pressed.action {
+            MenuAction::NextMsaa => settings.next_msaa(),
+            MenuAction::NextFrameLimit => settings.next_frame_limit(),
+            MenuAction::NextWindowMode => settings.next_window_mode(),
+            MenuAction::NextVolume => settings.next_volume(),
+            MenuAction::ToggleZoomToCursor => settings.zoom_to_cursor = !settings.zoom_to_cursor,
+            MenuAction::NextMaxZoomOut => settings.next_max_zoom_out(),
+            MenuAction::ResetSettings => *settings = GameSettings::default(),
+            MenuAction::Back => {
+                settings_state_next_state.set(SettingsState::Closed);
+                continue;
+            }
+            _ => continue,
+        }
+        save_settings(&settings);
+    }
+}
+
+pub fn update_settings_labels(
+    settings: Res<GameSettings>,
+    button_query: Query<(&Children, &MenuButton)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (children, menu_button) in button_query.iter() {
+        let label = match menu_button.action {
+            MenuAction::NextMsaa => match settings.msaa_samples {
+                1 => "Anti-aliasing: Off".to_string(),
+                samples => format!("Anti-aliasing: {samples}x"),
+            },
+            MenuAction::NextFrameLimit => match settings.frame_limit {
+                FrameLimit::Auto => "Frame limit: Display".to_string(),
+                FrameLimit::Fps(fps) => format!("Frame limit: {fps} FPS"),
+                FrameLimit::Off => "Frame limit: Off".to_string(),
+            },
+            MenuAction::NextWindowMode => match settings.window_mode {
+                WindowMode::Windowed => "Window: Windowed",
+                WindowMode::BorderlessFullscreen => "Window: Borderless",
+                WindowMode::SizedFullscreen | WindowMode::Fullscreen => "Window: Fullscreen",
+            }
+            .to_string(),
+            MenuAction::NextVolume => format!("Volume: {:.0}%", settings.volume * 100.0),
+            MenuAction::ToggleZoomToCursor => {
+                let state = if settings.zoom_to_cursor { "On" } else { "Off" };
+                format!("Zoom to cursor: {state}")
+            }
+            MenuAction::NextMaxZoomOut => format!("Max zoom out: {}x", settings.max_zoom_out),
+            _ => continue,
+        };
+        set_button_label(children, &mut text_query, &label);
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::menu::components::MenuAction;
+use crate::menu::screen::{MenuItem, MenuScreen};
+use crate::settings_menu::components::*;
+use crate::settings_menu::styles::*;
+
+pub fn spawn_settings_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    build_settings_menu(&mut commands, &asset_server);
+}
+
+pub fn despawn_settings_menu(
+    mut commands: Commands,
+    settings_menu_query: Query<Entity, With<SettingsMenu>>,
+) {
+    if let Ok(settings_menu_entity) = settings_menu_query.get_single() {
+        commands.entity(settings_menu_entity).despawn_recursive();
+    }
+}
+
+pub fn build_settings_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    // The labels show the current value of each option.
+    let setting = |action| MenuItem::Button {
+        label: String::new(),
+        action,
+        style: SETTING_BUTTON_STYLE,
+        font_size: SETTING_FONT_SIZE,
+    };
+
+    MenuScreen {
+        title: "Settings",
+        items: vec![
+            setting(MenuAction::NextMsaa),
+            setting(MenuAction::NextFrameLimit),
+            setting(MenuAction::NextWindowMode),
+            setting(MenuAction::NextVolume),
+            setting(MenuAction::ToggleZoomToCursor),
+            setting(MenuAction::NextMaxZoomOut),
+            MenuItem::row(vec![
+                MenuItem::button("Defaults", MenuAction::ResetSettings),
+                MenuItem::button("Back", MenuAction::Back),
+            ]),
+        ],
+        ..default()
+    }
+    .spawn(commands, asset_server, SettingsMenu {})
+}
@@ -0,0 +1,61 @@
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bevy_inspector_egui::prelude::*;
+
+use crate::tank::components::Team;
+
+/// Looks for enemies from its turret.
+#[derive(Copy, Clone, Debug, PartialEq, Component, Reflect, FromReflect, InspectorOptions)]
+#[reflect(Component, PartialEq)]
+pub struct Spotter {
+    #[inspector(min = 0.0, max = 5000.0)]
+    pub range: f32,
+}
+
+impl Default for Spotter {
+    fn default() -> Self {
+        Self { range: 600.0 }
+    }
+}
+
+/// Which teams can see this entity. Added to everything with a `Team`.
+#[derive(Clone, Debug, Default, Component)]
+pub struct Spotted {
+    /// Teams with a clear line of sight to the entity this frame.
+    pub by_team: HashSet<Team>,
+    /// Where each team saw the entity last.
+    pub last_seen: HashMap<Team, LastSeen>,
+}
+
+impl Spotted {
+    /// Whether `team` knows where the entity is right now. Teams always see their own.
+    pub fn is_visible_to(&self, own_team: Team, team: Team) -> bool {
+        own_team == team || self.by_team.contains(&team)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LastSeen {
+    pub position: Vec2,
+    /// `Time::elapsed_seconds` at the moment.
+    pub time: f32,
+}
+
+/// Hides entities the player's team has not spotted.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct FogOfWar {
+    pub enabled: bool,
+    /// The team whose view is rendered.
+    pub team: Team,
+}
+
+impl Default for FogOfWar {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            team: Team::Red,
+        }
+    }
+}
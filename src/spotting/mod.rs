@@ -0,0 +1,21 @@
+pub mod components;
+mod systems;
+
+use bevy::prelude::*;
+
+use components::*;
+use systems::*;
+
+use crate::GameplaySet;
+
+pub struct SpottingPlugin;
+
+impl Plugin for SpottingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Spotter>()
+            .init_resource::<FogOfWar>()
+            .add_system(add_spotted.before(spot_targets))
+            .add_system(spot_targets.in_set(GameplaySet))
+            .add_system(apply_fog_of_war.after(spot_targets));
+    }
+}
@@ -0,0 +1,95 @@
+use bevy::{prelude::*, utils::HashSet};
+use bevy_rapier2d::prelude::*;
+
+use super::components::*;
+use crate::gun::components::Turret;
+use crate::tank::components::Team;
+
+pub fn add_spotted(mut commands: Commands, team_query: Query<Entity, Added<Team>>) {
+    for entity in team_query.iter() {
+        commands.entity(entity).insert(Spotted::default());
+    }
+}
+
+/// Casts a ray from every spotter's turret to every entity of another team in range.
+pub fn spot_targets(
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    spotter_query: Query<(Entity, &Spotter, &Team, &GlobalTransform, Option<&Children>)>,
+    turret_query: Query<&GlobalTransform, With<Turret>>,
+    mut spotted_query: Query<(Entity, &mut Spotted, &Team, &GlobalTransform)>,
+) {
+    let eyes: Vec<(Entity, Team, Vec2, f32)> = spotter_query
+        .iter()
+        .map(|(entity, spotter, team, global_transform, children)| {
+            let turret = children
+                .into_iter()
+                .flatten()
+                .find_map(|&child| turret_query.get(child).ok())
+                .unwrap_or(global_transform);
+
+            (
+                entity,
+                *team,
+                turret.translation().truncate(),
+                spotter.range,
+            )
+        })
+        .collect();
+
+    for (entity, mut spotted, team, global_transform) in spotted_query.iter_mut() {
+        let position = global_transform.translation().truncate();
+        let mut seen_by = HashSet::new();
+
+        for &(spotter, spotter_team, eye, range) in &eyes {
+            if spotter_team == *team || seen_by.contains(&spotter_team) {
+                continue;
+            }
+
+            let offset = position - eye;
+            if offset.length() > range {
+                continue;
+            }
+
+            let filter = QueryFilter::new()
+                .exclude_rigid_body(spotter)
+                .exclude_sensors();
+            let visible = rapier_context
+                .cast_ray(eye, offset, 1.0, true, filter)
+                .is_none_or(|(hit, _)| hit == entity);
+
+            if visible {
+                seen_by.insert(spotter_team);
+            }
+        }
+
+        for &team in &seen_by {
+            spotted.last_seen.insert(
+                team,
+                LastSeen {
+                    position,
+                    time: time.elapsed_seconds(),
+                },
+            );
+        }
+        spotted.by_team = seen_by;
+    }
+}
+
+pub fn apply_fog_of_war(
+    fog_of_war: Res<FogOfWar>,
+    mut spotted_query: Query<(&Spotted, &Team, &mut Visibility)>,
+) {
+    for (spotted, team, mut visibility) in spotted_query.iter_mut() {
+        let visible = !fog_of_war.enabled || spotted.is_visible_to(*team, fog_of_war.team);
+        let wanted = if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+}
@@ -1,25 +1,74 @@
 use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use input_map::actions::{Action, ActionState};
 
-use crate::AppState;
+use crate::{AppState, GameSessionEntity, SimulationState};
 
 pub fn transition_to_main_menu_state(
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<ActionState>,
     app_state: Res<State<AppState>>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::M) && app_state.0 != AppState::MainMenu {
+    if actions.just_pressed(Action::MainMenu) && app_state.0 != AppState::MainMenu {
         app_state_next_state.set(AppState::MainMenu);
         println!("Entered AppState::MainMenu");
     }
 }
 
 pub fn transition_to_game_state(
-    keyboard_input: Res<Input<KeyCode>>,
+    actions: Res<ActionState>,
     app_state: Res<State<AppState>>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::G) && app_state.0 != AppState::Game {
+    if actions.just_pressed(Action::StartGame) && app_state.0 != AppState::Game {
         app_state_next_state.set(AppState::Game);
         println!("Entered AppState::Game");
     }
 }
+
+pub fn toggle_pause(
+    actions: Res<ActionState>,
+    simulation_state: Res<State<SimulationState>>,
+    mut simulation_state_next_state: ResMut<NextState<SimulationState>>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        match simulation_state.0 {
+            SimulationState::Running => simulation_state_next_state.set(SimulationState::Paused),
+            SimulationState::Paused => simulation_state_next_state.set(SimulationState::Running),
+        }
+    }
+}
+
+pub fn pause_physics(mut rapier_configuration: ResMut<RapierConfiguration>) {
+    rapier_configuration.physics_pipeline_active = false;
+}
+
+pub fn resume_physics(mut rapier_configuration: ResMut<RapierConfiguration>) {
+    rapier_configuration.physics_pipeline_active = true;
+}
+
+/// Leaving the game never leaves it paused for the next session.
+pub fn resume_simulation(mut simulation_state_next_state: ResMut<NextState<SimulationState>>) {
+    simulation_state_next_state.set(SimulationState::Running);
+}
+
+pub fn despawn_game_session(
+    mut commands: Commands,
+    session_query: Query<Entity, With<GameSessionEntity>>,
+) {
+    for entity in session_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Where the cursor is in world space, if it is over the window.
+pub fn get_cursor_position(
+    window: &Window,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) -> Option<Vec2> {
+    window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
+        .map(|ray| ray.origin.truncate())
+}
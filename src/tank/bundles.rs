@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::{path, prelude::*};
+
+use super::definition::HullDefinition;
+
+#[derive(Bundle)]
+pub struct TankBodyBundle {
+    #[bundle]
+    shape: ShapeBundle,
+    fill: Fill,
+    stroke: Stroke,
+}
+
+impl TankBodyBundle {
+    pub fn new(definition: &HullDefinition, transform: Transform) -> Self {
+        let body = shapes::Polygon {
+            points: definition.points.clone(),
+            closed: true,
+        };
+
+        // Chevron pointing towards the front of the hull.
+        let half_extents = definition.half_extents;
+        let mut path_builder = path::PathBuilder::new();
+        path_builder.move_to(Vec2::new(-half_extents.x, -half_extents.y));
+        path_builder.line_to(Vec2::new(half_extents.x, 0.0));
+        path_builder.line_to(Vec2::new(-half_extents.x, half_extents.y));
+        let path = path_builder.build();
+
+        let mut stroke = Stroke::new(definition.outline(), 2.0);
+        stroke.options.line_join = LineJoin::Round;
+
+        let mut fill = Fill::color(definition.color());
+        fill.options.fill_rule = FillRule::NonZero;
+
+        Self {
+            shape: ShapeBundle {
+                path: GeometryBuilder::new().add(&body).add(&path).build(),
+                transform,
+                ..default()
+            },
+            fill,
+            stroke,
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+
+use bevy::{asset::Asset, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use super::definition::*;
+
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+#[reflect(Component, PartialEq)]
+pub enum Team {
+    #[default]
+    Red,
+    Blue,
+}
+
+#[derive(Component)]
+pub struct Tank;
+
+/// What a tank is built from.
+#[derive(Clone, Debug)]
+pub enum TankBlueprint {
+    Preset(Handle<TankDefinition>),
+    Parts {
+        hull: Handle<HullDefinition>,
+        turret: Handle<TurretDefinition>,
+    },
+}
+
+impl TankBlueprint {
+    /// The assembled definition, or `None` while any part is still loading.
+    pub fn resolve(
+        &self,
+        tanks: &Assets<TankDefinition>,
+        hulls: &Assets<HullDefinition>,
+        turrets: &Assets<TurretDefinition>,
+    ) -> Option<TankDefinition> {
+        match self {
+            TankBlueprint::Preset(tank) => tanks.get(tank).cloned(),
+            TankBlueprint::Parts { hull, turret } => {
+                let hull = hulls.get(hull)?.clone();
+                let turret = turrets.get(turret)?.clone();
+                Some(TankDefinition { hull, turret })
+            }
+        }
+    }
+}
+
+/// Turns its entity into a tank as soon as the blueprint has finished loading.
+///
+/// Components already on the entity, such as `Player`, are kept.
+#[derive(Component)]
+pub struct TankSpawnRequest {
+    pub blueprint: TankBlueprint,
+    pub team: Team,
+    pub transform: Transform,
+}
+
+/// Hull and turret the player picked in the garage, as asset paths.
+#[derive(Resource, Clone, Debug, PartialEq, Eq)]
+pub struct Loadout {
+    pub hull: String,
+    pub turret: String,
+}
+
+impl Default for Loadout {
+    fn default() -> Self {
+        Self {
+            hull: "hulls/medium.hull.ron".to_string(),
+            turret: "turrets/cannon.turret.ron".to_string(),
+        }
+    }
+}
+
+impl Loadout {
+    pub fn blueprint(&self, asset_server: &AssetServer) -> TankBlueprint {
+        TankBlueprint::Parts {
+            hull: asset_server.load(self.hull.as_str()),
+            turret: asset_server.load(self.turret.as_str()),
+        }
+    }
+}
+
+/// Every hull and turret found in the asset folders.
+#[derive(Resource, Default)]
+pub struct Garage {
+    pub hulls: Vec<Handle<HullDefinition>>,
+    pub turrets: Vec<Handle<TurretDefinition>>,
+}
+
+impl Garage {
+    pub fn next_hull(&self, current: &str, asset_server: &AssetServer) -> Option<String> {
+        next_part(&self.hulls, current, asset_server)
+    }
+
+    pub fn next_turret(&self, current: &str, asset_server: &AssetServer) -> Option<String> {
+        next_part(&self.turrets, current, asset_server)
+    }
+}
+
+fn next_part<T: Asset>(
+    parts: &[Handle<T>],
+    current: &str,
+    asset_server: &AssetServer,
+) -> Option<String> {
+    let mut paths: Vec<String> = parts
+        .iter()
+        .filter_map(|part| asset_server.get_handle_path(part))
+        .map(|path| asset_path_string(path.path()))
+        .collect();
+    paths.sort();
+
+    let next = paths
+        .iter()
+        .position(|path| path == current)
+        .map_or(0, |index| (index + 1) % paths.len());
+
+    paths.get(next).cloned()
+}
+
+fn asset_path_string(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
@@ -0,0 +1,121 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    asset::{Asset, AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{
+    combat::components::Armor,
+    gun::components::{Gun, Turret},
+    tracks::components::Tracks,
+};
+
+/// A hull from `assets/hulls/*.hull.ron`.
+///
+/// All lengths are in pixels and the hull faces +X.
+#[derive(Debug, Clone, Deserialize, TypeUuid)]
+#[uuid = "5b0f8d3e-2f4c-4a7e-9c55-0e3d6a1b7c21"]
+pub struct HullDefinition {
+    pub name: String,
+    /// Hex colour of the hull.
+    pub color: String,
+    /// Hex colour of the outline.
+    pub outline: String,
+    /// Closed hull polygon.
+    pub points: Vec<Vec2>,
+    /// Half extents of the hull's box collider.
+    pub half_extents: Vec2,
+    /// Where the turret sits, in the hull's local space.
+    pub turret_mount: Vec2,
+    pub mass: f32,
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+    pub restitution: f32,
+    pub health: f32,
+    pub armor: Armor,
+    /// How far the crew can spot enemies from the turret.
+    pub view_range: f32,
+    pub tracks: Tracks,
+}
+
+/// A turret and its gun from `assets/turrets/*.turret.ron`.
+#[derive(Debug, Clone, Deserialize, TypeUuid)]
+#[uuid = "c4a1f6f2-93b8-4d1e-8f07-6b2d9e4a3f10"]
+pub struct TurretDefinition {
+    pub name: String,
+    /// Hex colour of the turret and gun.
+    pub color: String,
+    /// Hex colour of the outlines.
+    pub outline: String,
+    pub radius: f32,
+    pub turret: Turret,
+    /// Length and width of the barrel. Shells leave at its far end.
+    pub gun_size: Vec2,
+    pub gun: Gun,
+}
+
+/// A complete tank, either assembled from a `Loadout` or loaded as a preset
+/// from `assets/tanks/*.tank.ron`.
+#[derive(Debug, Clone, Deserialize, TypeUuid)]
+#[uuid = "ea8de35a-7b7a-44d5-8954-c9d95b1d5d27"]
+pub struct TankDefinition {
+    pub hull: HullDefinition,
+    pub turret: TurretDefinition,
+}
+
+impl HullDefinition {
+    pub fn color(&self) -> Color {
+        Color::hex(&self.color).unwrap_or(Color::GRAY)
+    }
+
+    pub fn outline(&self) -> Color {
+        Color::hex(&self.outline).unwrap_or(Color::BLACK)
+    }
+}
+
+impl TurretDefinition {
+    pub fn color(&self) -> Color {
+        Color::hex(&self.color).unwrap_or(Color::GRAY)
+    }
+
+    pub fn outline(&self) -> Color {
+        Color::hex(&self.outline).unwrap_or(Color::BLACK)
+    }
+}
+
+/// Loads any deserializable asset from a RON file with one of `extensions`.
+pub struct RonAssetLoader<T> {
+    extensions: &'static [&'static str],
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> RonAssetLoader<T> {
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset + DeserializeOwned> AssetLoader for RonAssetLoader<T> {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let asset = ron::de::from_bytes::<T>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(asset));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
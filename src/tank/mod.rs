@@ -0,0 +1,28 @@
+pub mod bundles;
+pub mod components;
+pub mod definition;
+pub mod systems;
+
+use bevy::prelude::*;
+
+use components::*;
+use definition::*;
+use systems::*;
+
+pub struct TankPlugin;
+
+impl Plugin for TankPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Team>()
+            .init_resource::<Garage>()
+            .init_resource::<Loadout>()
+            .add_asset::<HullDefinition>()
+            .add_asset::<TurretDefinition>()
+            .add_asset::<TankDefinition>()
+            .add_asset_loader(RonAssetLoader::<HullDefinition>::new(&["hull.ron"]))
+            .add_asset_loader(RonAssetLoader::<TurretDefinition>::new(&["turret.ron"]))
+            .add_asset_loader(RonAssetLoader::<TankDefinition>::new(&["tank.ron"]))
+            .add_startup_system(load_garage)
+            .add_system(spawn_requested_tanks);
+    }
+}
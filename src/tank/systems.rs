@@ -0,0 +1,151 @@
+use bevy::{asset::Asset, ecs::system::EntityCommands, prelude::*};
+use bevy_prototype_lyon::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::bundles::TankBodyBundle;
+use super::components::*;
+use super::definition::*;
+use crate::combat::components::Health;
+use crate::gun::components::Gun;
+use crate::spotting::components::Spotter;
+
+/// Turns the entity behind `commands` into the hull of a tank and spawns its gun and turret.
+///
+/// Components already on the entity are kept, so callers can add their own markers first.
+pub fn spawn_tank(
+    commands: &mut EntityCommands,
+    definition: &TankDefinition,
+    team: Team,
+    transform: Transform,
+) -> Entity {
+    let hull = commands.id();
+
+    let TankDefinition {
+        hull: hull_definition,
+        turret: turret_definition,
+    } = definition;
+
+    let turret = shapes::Circle {
+        radius: turret_definition.radius,
+        ..default()
+    };
+
+    let gun_size = turret_definition.gun_size;
+    let gun = shapes::Polygon {
+        points: vec![
+            Vec2::new(0.0, -gun_size.y / 2.0),
+            Vec2::new(0.0, gun_size.y / 2.0),
+            Vec2::new(gun_size.x, gun_size.y / 2.0),
+            Vec2::new(gun_size.x, -gun_size.y / 2.0),
+        ],
+        closed: true,
+    };
+
+    commands.insert((
+        Name::new(hull_definition.name.clone()),
+        TankBodyBundle::new(hull_definition, transform),
+        Tank,
+        team,
+        hull_definition.tracks,
+        (
+            RigidBody::Dynamic,
+            Damping {
+                linear_damping: hull_definition.linear_damping,
+                angular_damping: hull_definition.angular_damping,
+            },
+            Velocity::default(),
+            ExternalForce::default(),
+            ExternalImpulse::default(),
+            ReadMassProperties::default(),
+            Collider::cuboid(
+                hull_definition.half_extents.x,
+                hull_definition.half_extents.y,
+            ),
+            ColliderMassProperties::Mass(hull_definition.mass),
+            Restitution::coefficient(hull_definition.restitution),
+        ),
+        Health::new(hull_definition.health),
+        hull_definition.armor,
+        Spotter {
+            range: hull_definition.view_range,
+        },
+    ));
+
+    let gun = commands
+        .commands()
+        .spawn((
+            ShapeBundle {
+                path: GeometryBuilder::new().add(&gun).build(),
+                transform: Transform {
+                    translation: hull_definition.turret_mount.extend(0.1),
+                    ..default()
+                },
+                ..default()
+            },
+            Fill::color(turret_definition.color()),
+            Stroke::new(turret_definition.outline(), 2.0),
+            Name::new(format!("{} Gun", turret_definition.name)),
+            Gun {
+                muzzle_offset: Vec2::new(gun_size.x, 0.0),
+                ..turret_definition.gun
+            },
+            turret_definition.turret,
+        ))
+        .id();
+
+    let turret = commands
+        .commands()
+        .spawn((
+            ShapeBundle {
+                path: GeometryBuilder::new().add(&turret).build(),
+                transform: Transform {
+                    translation: Vec3::new(0.0, 0.0, 0.2),
+                    ..default()
+                },
+                ..default()
+            },
+            Fill::color(turret_definition.color()),
+            Stroke::new(turret_definition.outline(), 2.0),
+            Name::new(format!("{} Turret", turret_definition.name)),
+        ))
+        .id();
+
+    commands.add_child(gun);
+    commands.commands().entity(gun).add_child(turret);
+
+    hull
+}
+
+pub fn load_garage(mut garage: ResMut<Garage>, asset_server: Res<AssetServer>) {
+    garage.hulls = load_parts(&asset_server, "hulls");
+    garage.turrets = load_parts(&asset_server, "turrets");
+}
+
+fn load_parts<T: Asset>(asset_server: &AssetServer, folder: &str) -> Vec<Handle<T>> {
+    match asset_server.load_folder(folder) {
+        Ok(handles) => handles
+            .into_iter()
+            .map(|handle| handle.typed::<T>())
+            .collect(),
+        Err(error) => {
+            warn!("Could not load garage parts from {folder}: {error:?}");
+            Vec::new()
+        }
+    }
+}
+
+pub fn spawn_requested_tanks(
+    mut commands: Commands,
+    tanks: Res<Assets<TankDefinition>>,
+    hulls: Res<Assets<HullDefinition>>,
+    turrets: Res<Assets<TurretDefinition>>,
+    request_query: Query<(Entity, &TankSpawnRequest)>,
+) {
+    for (entity, request) in request_query.iter() {
+        if let Some(definition) = request.blueprint.resolve(&tanks, &hulls, &turrets) {
+            let mut tank = commands.entity(entity);
+            tank.remove::<TankSpawnRequest>();
+            spawn_tank(&mut tank, &definition, request.team, request.transform);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::prelude::*;
+use serde::Deserialize;
+
+/// Differential-drive controller for a tracked hull.
+///
+/// `throttle` and `steer` are the driver's input in `-1.0..=1.0` and are written
+/// by whoever drives the hull (player input or AI). Positive `steer` turns left.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Component, Reflect, FromReflect, InspectorOptions, Deserialize,
+)]
+#[reflect(Component, PartialEq)]
+#[serde(default)]
+pub struct Tracks {
+    #[inspector(min = 0.0, max = 2000.0)]
+    pub max_speed: f32,
+    #[inspector(min = 0.0, max = 10000.0)]
+    pub acceleration: f32,
+    /// Degrees per second.
+    #[inspector(min = 0.0, max = 720.0)]
+    pub turn_rate: f32,
+    /// Distance between the left and right track.
+    #[inspector(min = 0.0, max = 200.0)]
+    pub track_width: f32,
+    /// Largest acceleration a single track can transfer to the ground before it slips.
+    #[inspector(min = 0.0, max = 10000.0)]
+    pub traction: f32,
+    #[inspector(min = -1.0, max = 1.0)]
+    pub throttle: f32,
+    #[inspector(min = -1.0, max = 1.0)]
+    pub steer: f32,
+    /// Multiplier on `max_speed` from the ground under the hull, e.g. `< 1.0` in water.
+    #[inspector(min = 0.0, max = 1.0)]
+    pub speed_factor: f32,
+    /// When `false` the hull is moved by something else and no track forces are applied.
+    pub engaged: bool,
+    /// How much of the requested force each track lost to slipping, `0.0..=1.0`.
+    pub left_slip: f32,
+    pub right_slip: f32,
+}
+
+impl Default for Tracks {
+    fn default() -> Self {
+        Self {
+            max_speed: 250.0,
+            acceleration: 800.0,
+            turn_rate: 90.0,
+            track_width: 30.0,
+            traction: 1200.0,
+            throttle: 0.0,
+            steer: 0.0,
+            speed_factor: 1.0,
+            engaged: true,
+            left_slip: 0.0,
+            right_slip: 0.0,
+        }
+    }
+}
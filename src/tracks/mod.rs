@@ -0,0 +1,18 @@
+pub mod components;
+mod systems;
+
+use bevy::prelude::*;
+
+use components::*;
+use systems::*;
+
+use crate::GameplaySet;
+
+pub struct TracksPlugin;
+
+impl Plugin for TracksPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Tracks>()
+            .add_system(track_drive.in_set(GameplaySet));
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::components::*;
+
+/// How quickly tracks try to close the gap between their current and target speed, 1/s.
+const TRACK_RESPONSE: f32 = 10.0;
+
+pub fn track_drive(
+    mut tracks_query: Query<(
+        &mut Tracks,
+        &Velocity,
+        &Transform,
+        &ReadMassProperties,
+        &mut ExternalForce,
+    )>,
+) {
+    for (mut tracks, velocity, transform, mass_properties, mut external_force) in
+        tracks_query.iter_mut()
+    {
+        let mass = mass_properties.0.mass;
+
+        if !tracks.engaged || mass <= 0.0 {
+            *external_force = ExternalForce::default();
+            tracks.left_slip = 0.0;
+            tracks.right_slip = 0.0;
+            continue;
+        }
+
+        let forward = (transform.rotation * Vec3::X).truncate();
+        let left = forward.perp();
+        let center = transform.translation.truncate();
+
+        let target_speed =
+            tracks.throttle.clamp(-1.0, 1.0) * tracks.max_speed * tracks.speed_factor;
+        let target_angvel = tracks.steer.clamp(-1.0, 1.0) * tracks.turn_rate.to_radians();
+        let half_width = tracks.track_width / 2.0;
+
+        let mut force = ExternalForce::default();
+        let mut slip = [0.0; 2];
+
+        for (track, offset) in [half_width, -half_width].into_iter().enumerate() {
+            let lever = left * offset;
+            let ground_velocity = velocity.linvel + velocity.angvel * lever.perp();
+
+            // The inner track slows down and the outer one speeds up to turn the hull.
+            let track_target = target_speed - target_angvel * offset;
+            let longitudinal = ((track_target - ground_velocity.dot(forward)) * TRACK_RESPONSE)
+                .clamp(-tracks.acceleration, tracks.acceleration);
+            let lateral = -ground_velocity.dot(left) * TRACK_RESPONSE;
+
+            let mut acceleration = forward * longitudinal + left * lateral;
+            let demand = acceleration.length();
+
+            if demand > tracks.traction {
+                slip[track] = 1.0 - tracks.traction / demand;
+                acceleration *= tracks.traction / demand;
+            }
+
+            force += ExternalForce::at_point(acceleration * mass / 2.0, center + lever, center);
+        }
+
+        *external_force = force;
+        tracks.left_slip = slip[0];
+        tracks.right_slip = slip[1];
+    }
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+/// The button that Enter or the gamepad's south button presses.
+#[derive(Resource, Default)]
+pub struct UiFocus {
+    pub focused: Option<Entity>,
+    /// Set while a screen reads the keys itself, like the controls screen while rebinding.
+    pub suspended: bool,
+}
@@ -0,0 +1,27 @@
+pub mod components;
+mod navigation;
+mod systems;
+
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+use components::*;
+use systems::*;
+
+/// Lets every menu be used with the keyboard or a gamepad instead of the mouse.
+pub struct UiFocusPlugin;
+
+impl Plugin for UiFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiFocus>()
+            // Presses from the keyboard or gamepad reach the menus in the same frame as
+            // clicks from the mouse.
+            .add_systems(
+                (focus_hovered_button, navigate_focus)
+                    .chain()
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(UiSystem::Focus),
+            )
+            .add_system(highlight_focused_button.in_base_set(CoreSet::PostUpdate));
+    }
+}
@@ -0,0 +1,104 @@
+use std::cmp::Ordering;
+
+use bevy::math::Vec2;
+
+/// How much sideways distance counts against a candidate compared to distance ahead.
+const ACROSS_PENALTY: f32 = 2.0;
+
+/// The node after `from` in `direction`, preferring nodes in line with it over closer
+/// ones off to the side. Positions are in UI space, where y grows downwards.
+pub fn neighbour<T: Copy>(
+    from: Vec2,
+    direction: Vec2,
+    nodes: impl IntoIterator<Item = (T, Vec2)>,
+) -> Option<T> {
+    nodes
+        .into_iter()
+        .filter_map(|(node, position)| {
+            let offset = position - from;
+            let along = offset.dot(direction);
+            // Nodes beside `from` in the same row or column are not ahead of it.
+            if along < 1.0 {
+                return None;
+            }
+            Some((
+                node,
+                along + offset.perp_dot(direction).abs() * ACROSS_PENALTY,
+            ))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(node, _)| node)
+}
+
+/// Sorts nodes top to bottom, then left to right, the way Tab moves through them.
+pub fn sort_in_reading_order<T>(nodes: &mut [(T, Vec2)]) {
+    nodes.sort_by(|(_, a), (_, b)| match a.y.total_cmp(&b.y) {
+        Ordering::Equal => a.x.total_cmp(&b.x),
+        ordering => ordering,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A column of three buttons above a row of two.
+    fn menu() -> Vec<(&'static str, Vec2)> {
+        vec![
+            ("play", Vec2::new(200.0, 100.0)),
+            ("level", Vec2::new(200.0, 200.0)),
+            ("seed", Vec2::new(200.0, 300.0)),
+            ("editor", Vec2::new(100.0, 400.0)),
+            ("quit", Vec2::new(300.0, 400.0)),
+        ]
+    }
+
+    #[test]
+    fn moves_to_the_nearest_node_ahead() {
+        let down = Vec2::new(0.0, 1.0);
+
+        assert_eq!(
+            neighbour(Vec2::new(200.0, 100.0), down, menu()),
+            Some("level")
+        );
+        assert_eq!(
+            neighbour(Vec2::new(200.0, 200.0), -down, menu()),
+            Some("play")
+        );
+    }
+
+    #[test]
+    fn moves_along_a_row() {
+        let right = Vec2::new(1.0, 0.0);
+
+        assert_eq!(
+            neighbour(Vec2::new(100.0, 400.0), right, menu()),
+            Some("quit")
+        );
+        assert_eq!(neighbour(Vec2::new(300.0, 400.0), right, menu()), None);
+    }
+
+    #[test]
+    fn prefers_nodes_in_line() {
+        let nodes = [
+            ("near", Vec2::new(150.0, 60.0)),
+            ("in_line", Vec2::new(0.0, 100.0)),
+        ];
+
+        assert_eq!(
+            neighbour(Vec2::ZERO, Vec2::new(0.0, 1.0), nodes),
+            Some("in_line")
+        );
+    }
+
+    #[test]
+    fn reading_order_is_by_rows() {
+        let mut nodes = menu();
+        nodes.reverse();
+
+        sort_in_reading_order(&mut nodes);
+
+        let order: Vec<&str> = nodes.iter().map(|(node, _)| *node).collect();
+        assert_eq!(order, ["play", "level", "seed", "editor", "quit"]);
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+use input_map::bindings::ActiveGamepad;
+
+use super::components::*;
+use super::navigation::*;
+use crate::menu::{
+    components::MenuButton,
+    events::MenuButtonPressed,
+    styles::{FOCUSED_BUTTON_COLOR, NORMAL_BUTTON_COLOR},
+};
+
+type FocusableButton<'a> = (Entity, &'a GlobalTransform, &'a MenuButton);
+
+/// Buttons the mouse moves over take the focus, so the keyboard carries on from there.
+pub fn focus_hovered_button(
+    button_query: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
+    mut focus: ResMut<UiFocus>,
+) {
+    for (entity, interaction) in button_query.iter() {
+        if *interaction == Interaction::Hovered && focus.focused != Some(entity) {
+            focus.focused = Some(entity);
+        }
+    }
+}
+
+/// Moves the focus with Tab, the arrow keys and the D-pad, and presses the focused button
+/// with Enter or the south button, just like a click would.
+pub fn navigate_focus(
+    keyboard_input: Res<Input<KeyCode>>,
+    active_gamepad: Res<ActiveGamepad>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut focus: ResMut<UiFocus>,
+    button_query: Query<FocusableButton>,
+    mut pressed_events: EventWriter<MenuButtonPressed>,
+) {
+    if focus
+        .focused
+        .is_some_and(|entity| !button_query.contains(entity))
+    {
+        focus.focused = None;
+    }
+    if focus.suspended {
+        return;
+    }
+
+    let gamepad_pressed = |button_type| {
+        active_gamepad.0.is_some_and(|gamepad| {
+            gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type))
+        })
+    };
+    let shift = keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    let tab = keyboard_input.just_pressed(KeyCode::Tab);
+
+    // UI space has y growing downwards.
+    let direction = [
+        (KeyCode::Up, GamepadButtonType::DPadUp, Vec2::NEG_Y),
+        (KeyCode::Down, GamepadButtonType::DPadDown, Vec2::Y),
+        (KeyCode::Left, GamepadButtonType::DPadLeft, Vec2::NEG_X),
+        (KeyCode::Right, GamepadButtonType::DPadRight, Vec2::X),
+    ]
+    .into_iter()
+    .find(|(key_code, button_type, _)| {
+        keyboard_input.just_pressed(*key_code) || gamepad_pressed(*button_type)
+    })
+    .map(|(_, _, direction)| direction);
+
+    let mut buttons: Vec<(Entity, Vec2)> = button_query
+        .iter()
+        .map(|(entity, global_transform, _)| (entity, global_transform.translation().truncate()))
+        .collect();
+    sort_in_reading_order(&mut buttons);
+
+    let current = focus
+        .focused
+        .and_then(|entity| buttons.iter().position(|(button, _)| *button == entity));
+
+    let next = match (current, tab, direction) {
+        (None, true, _) | (None, _, Some(_)) => buttons.first().map(|(entity, _)| *entity),
+        (Some(index), true, _) => {
+            let count = buttons.len();
+            let next = if shift { index + count - 1 } else { index + 1 };
+            Some(buttons[next % count].0)
+        }
+        (Some(index), false, Some(direction)) => {
+            let from = buttons[index].1;
+            neighbour(from, direction, buttons.iter().copied()).or(focus.focused)
+        }
+        _ => focus.focused,
+    };
+    if next != focus.focused {
+        focus.focused = next;
+    }
+
+    let activate =
+        keyboard_input.just_pressed(KeyCode::Return) || gamepad_pressed(GamepadButtonType::South);
+
+    if let (true, Some(entity)) = (activate, focus.focused) {
+        if let Ok((_, _, menu_button)) = button_query.get(entity) {
+            pressed_events.send(MenuButtonPressed {
+                action: menu_button.action,
+            });
+        }
+    }
+}
+
+/// Runs after the menus coloured their buttons, so the focus stays visible.
+pub fn highlight_focused_button(
+    focus: Res<UiFocus>,
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), With<Button>>,
+    mut highlighted: Local<Option<Entity>>,
+) {
+    if *highlighted != focus.focused {
+        if let Some((interaction, mut background_color)) =
+            highlighted.and_then(|entity| button_query.get_mut(entity).ok())
+        {
+            if *interaction == Interaction::None {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+        *highlighted = focus.focused;
+    }
+
+    if let Some((interaction, mut background_color)) = focus
+        .focused
+        .and_then(|entity| button_query.get_mut(entity).ok())
+    {
+        if *interaction == Interaction::None && background_color.0 != FOCUSED_BUTTON_COLOR {
+            *background_color = FOCUSED_BUTTON_COLOR.into();
+        }
+    }
+}